@@ -0,0 +1,92 @@
+use crate::generator::{Algorithm, Event, Generator, Steps};
+use crate::maze::{Cell, Maze};
+use rand::seq::IteratorRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::{HashSet, VecDeque};
+
+/// Generator using the Aldous-Broder algorithm, which picks uniformly from all perfect mazes.
+/// In pseudocode the algorithm looks like this:
+/// ```text
+/// cell = start
+/// while maze.has_unvisited_cells() {
+///     neighbor = cell.random_neighbor();
+///     if !neighbor.visited() {
+///         maze.carve(cell, neighbor);
+///     }
+///     cell = neighbor;
+/// }
+/// ```
+pub struct AldousBroder;
+
+impl Generator for AldousBroder {
+    fn name(&self) -> &'static str {
+        "aldous_broder"
+    }
+
+    fn description(&self) -> &'static str {
+        "Aldous-Broder random walk, uniformly random perfect mazes"
+    }
+
+    fn steps(&self, maze: Maze, seed: u64) -> Steps {
+        // Initialize variables for the random walk.
+        let search = Search {
+            total: maze.cells().count(),
+            current: maze.start(),
+            visited: HashSet::new(),
+        };
+
+        Steps::new(maze, ChaCha8Rng::seed_from_u64(seed), search)
+    }
+}
+
+/// State of the Aldous-Broder random walk.
+struct Search {
+    total: usize,
+    current: Cell,
+    visited: HashSet<Cell>,
+}
+
+impl Algorithm for Search {
+    fn step(
+        &mut self,
+        maze: &mut Maze,
+        rng: &mut ChaCha8Rng,
+        events: &mut VecDeque<Event>,
+    ) -> bool {
+        // The walk starts by visiting the start.
+        if self.visited.is_empty() {
+            self.visited.insert(self.current);
+            events.push_back(Event::CellEntered {
+                cell: self.current,
+                from: None,
+            });
+            return true;
+        }
+
+        // Stop once every cell has been visited.
+        if self.visited.len() == self.total {
+            events.push_back(Event::WalkerStopped(self.current));
+            return false;
+        }
+
+        let next = maze
+            .neighbors(self.current)
+            .choose(rng)
+            .expect("cells in a maze with more than one cell have neighbors");
+
+        // Only carve into cells that have not been visited yet.
+        if self.visited.insert(next) {
+            maze.carve(self.current, next);
+            events.push_back(Event::WallCarved(self.current, next));
+        }
+
+        events.push_back(Event::WalkerMoved {
+            from: self.current,
+            to: next,
+        });
+        self.current = next;
+
+        true
+    }
+}
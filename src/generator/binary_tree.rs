@@ -0,0 +1,91 @@
+use crate::generator::{Algorithm, Bias, Event, Generator, Steps};
+use crate::maze::Maze;
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::VecDeque;
+
+/// Generator using the binary tree algorithm, which needs no memory besides the current cell.
+/// In pseudocode the algorithm looks like this:
+/// ```text
+/// for cell in maze.cells() {
+///     maze.carve(cell, random_of(cell.vertical_neighbor(), cell.horizontal_neighbor()));
+/// }
+/// ```
+///
+/// The sides of the neighbors are given by the bias, which results in two long corridors along those sides.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct BinaryTree {
+    bias: Bias,
+}
+
+impl BinaryTree {
+    /// Returns generator leaning towards the given bias.
+    pub fn new(bias: Bias) -> BinaryTree {
+        BinaryTree { bias }
+    }
+}
+
+impl Generator for BinaryTree {
+    fn name(&self) -> &'static str {
+        "binary_tree"
+    }
+
+    fn description(&self) -> &'static str {
+        "Binary tree, carves towards one of two sides of every cell, strongly biased"
+    }
+
+    fn supports_mask(&self) -> bool {
+        false
+    }
+
+    fn steps(&self, maze: Maze, seed: u64) -> Steps {
+        let search = Search {
+            bias: self.bias,
+            index: 0,
+        };
+
+        Steps::new(maze, ChaCha8Rng::seed_from_u64(seed), search)
+    }
+}
+
+/// State of the binary tree algorithm.
+struct Search {
+    bias: Bias,
+    /// Index of the next cell, counted row by row.
+    index: u32,
+}
+
+impl Algorithm for Search {
+    fn step(
+        &mut self,
+        maze: &mut Maze,
+        rng: &mut ChaCha8Rng,
+        events: &mut VecDeque<Event>,
+    ) -> bool {
+        // Stop once every cell has been handled.
+        let columns = maze.columns() as u32;
+        if self.index == maze.rows() as u32 * columns {
+            return false;
+        }
+
+        let cell = ((self.index % columns) as u16, (self.index / columns) as u16);
+        self.index += 1;
+
+        events.push_back(Event::CellEntered { cell, from: None });
+
+        // Carve towards one of the neighbors on the sides of the bias.
+        let neighbors = [self.bias.vertical(), self.bias.horizontal()]
+            .into_iter()
+            .filter_map(|side| maze.neighbor(cell, side))
+            .collect_vec();
+
+        if let Some(&neighbor) = neighbors.choose(rng) {
+            maze.carve(cell, neighbor);
+            events.push_back(Event::WallCarved(cell, neighbor));
+        }
+
+        true
+    }
+}
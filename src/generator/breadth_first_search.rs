@@ -1,16 +1,14 @@
-use crate::maze::{Maze, Wall};
-use anyhow::Result;
-use crossterm::cursor::MoveTo;
-use crossterm::QueueableCommand;
+use crate::generator::{Algorithm, Event, Generator, Steps};
+use crate::maze::{Cell, Maze};
+use itertools::Itertools;
 use rand::seq::SliceRandom;
-use spin_sleep::sleep;
-use std::collections::HashSet;
-use std::io::Stdout;
-use std::time::Duration;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::{HashSet, VecDeque};
 
-/// Generates and draws a maze in the terminal using a randomized breadth-first search.
+/// Generator using a randomized breadth-first search.
 /// In pseudocode the algorithm looks like this:
-/// ```
+/// ```text
 /// stack.push(start)
 /// while let Some(cell) = stack.peek() {
 ///     if cell.has_unvisited_neighbor(){
@@ -22,144 +20,77 @@ use std::time::Duration;
 ///     }
 /// }
 /// ```
-pub fn generate(stdout: &mut Stdout, rows: u16, columns: u16, delay: u64) -> Result<Maze> {
-    if delay == 0 {
-        return generate_instant(stdout, rows, columns);
-    }
-
-    // Create a new walled maze of the specified dimensions.
-    let mut maze = Maze::new_walled(rows, columns);
-
-    // Draw the initial maze in the terminal.
-    maze.print(stdout)?;
-
-    // Initialize variables for breadth first search algorithm.
-    let mut visited = HashSet::<(u16, u16)>::new();
-    let mut unvisited = Vec::new();
-    unvisited.push((0, 0));
-
-    // Directions to try for neighbors and rng to randomize it.
-    let mut offsets = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-    let mut rng = rand::thread_rng();
-
-    // Main loop to iterate through the stack.
-    'top: while let Some(&(x, y)) = unvisited.last() {
-        sleep(Duration::from_millis(delay));
-        visited.insert((x, y));
-
-        // Calculate the frame indices of the current cell.
-        let (cx, cy) = (2 * x + 1, y + 1);
-
-        // Redraw the current cell, removing previously overwritten characters.
-        stdout.queue(MoveTo(cx, cy))?;
-        maze.get_wall(cx, cy).print(stdout)?;
-
-        // Randomize order of directions to try.
-        offsets.shuffle(&mut rng);
-        for (dx, dy) in offsets {
-            let (nx, ny) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
-
-            // Skip out of bounds coordinates.
-            if !(0..columns).contains(&nx) || !(0..rows).contains(&ny) {
-                continue;
-            }
-
-            // Skip if current neighbor has been visited.
-            if visited.contains(&(nx, ny)) || unvisited.contains(&(nx, ny)) {
-                continue;
-            }
-
-            unvisited.push((nx, ny));
-
-            // Update wall between current and next cell.
-            let wx = x + nx + 1;
-            let wy = if dy == -1 { ny } else { y } + 1;
-            stdout.queue(MoveTo(wx, wy))?;
-
-            if dx == 0 {
-                Wall::None(' ').print(stdout)?;
-                maze.set_wall(wx, wy, Wall::None(' '));
-            } else {
-                Wall::Horizontal(' ').print(stdout)?;
-                maze.set_wall(wx, wy, Wall::Horizontal(' '));
-            }
+pub struct BreadthFirstSearch;
 
-            // Calculate the frame indices of the neighboring cell.
-            let (cx, cy) = (2 * nx + 1, ny + 1);
-
-            // Print central dot in neighboring cell.
-            stdout.queue(MoveTo(cx, cy))?;
-            maze.get_wall(cx, cy).print_with_char(stdout, '·')?;
-
-            unvisited.shuffle(&mut rng);
-            continue 'top;
-        }
+impl Generator for BreadthFirstSearch {
+    fn name(&self) -> &'static str {
+        "breadth_first_search"
+    }
 
-        // No more neighbors to visit at this cell, so pop it.
-        unvisited.pop();
+    fn description(&self) -> &'static str {
+        "Randomized search over a shuffled stack, many short branches"
     }
 
-    // Set cursor after the maze.
-    stdout.queue(MoveTo(0, rows + 1))?;
+    fn steps(&self, maze: Maze, seed: u64) -> Steps {
+        // Initialize variables for breadth first search algorithm.
+        let search = Search {
+            visited: HashSet::new(),
+            unvisited: vec![maze.start()],
+        };
 
-    Ok(maze)
+        Steps::new(maze, ChaCha8Rng::seed_from_u64(seed), search)
+    }
 }
 
-/// Stripped version of `generate()` that *only* draws at the end of generation.
-fn generate_instant(stdout: &mut Stdout, rows: u16, columns: u16) -> Result<Maze> {
-    // Create a new walled maze of the specified dimensions.
-    let mut maze = Maze::new_walled(rows, columns);
-
-    // Initialize variables for breadth first search algorithm.
-    let mut visited = HashSet::<(u16, u16)>::new();
-    let mut unvisited = Vec::new();
-    unvisited.push((0, 0));
-
-    // Directions to try for neighbors and rng to randomize it.
-    let mut offsets = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-    let mut rng = rand::thread_rng();
-
-    // Main loop to iterate through the stack.
-    'top: while let Some(&(x, y)) = unvisited.last() {
-        visited.insert((x, y));
-
-        // Randomize order of directions to try.
-        offsets.shuffle(&mut rng);
-        for (dx, dy) in offsets {
-            let (nx, ny) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+/// State of the breadth-first search.
+struct Search {
+    visited: HashSet<Cell>,
+    unvisited: Vec<Cell>,
+}
 
-            // Skip out of bounds coordinates.
-            if !(0..columns).contains(&nx) || !(0..rows).contains(&ny) {
-                continue;
-            }
+impl Algorithm for Search {
+    fn step(
+        &mut self,
+        maze: &mut Maze,
+        rng: &mut ChaCha8Rng,
+        events: &mut VecDeque<Event>,
+    ) -> bool {
+        let Some(&(x, y)) = self.unvisited.last() else {
+            return false;
+        };
+
+        if self.visited.insert((x, y)) {
+            events.push_back(Event::CellEntered {
+                cell: (x, y),
+                from: None,
+            });
+        }
 
-            // Skip if current neighbor has been visited.
-            if visited.contains(&(nx, ny)) || unvisited.contains(&(nx, ny)) {
-                continue;
-            }
+        // Pick a random neighbor that has not been visited or added to the stack yet.
+        let neighbors = maze
+            .neighbors((x, y))
+            .filter(|neighbor| {
+                !self.visited.contains(neighbor) && !self.unvisited.contains(neighbor)
+            })
+            .collect_vec();
 
-            unvisited.push((nx, ny));
+        if let Some(&neighbor) = neighbors.choose(rng) {
+            self.unvisited.push(neighbor);
 
             // Update wall between current and next cell.
-            let wx = x + nx + 1;
-            let wy = if dy == -1 { ny } else { y } + 1;
+            maze.carve((x, y), neighbor);
 
-            if dx == 0 {
-                maze.set_wall(wx, wy, Wall::None(' '));
-            } else {
-                maze.set_wall(wx, wy, Wall::Horizontal(' '));
-            }
+            events.push_back(Event::WallCarved((x, y), neighbor));
+            events.push_back(Event::FrontierAdded(neighbor));
 
-            unvisited.shuffle(&mut rng);
-            continue 'top;
+            self.unvisited.shuffle(rng);
+            return true;
         }
 
         // No more neighbors to visit at this cell, so pop it.
-        unvisited.pop();
-    }
-
-    // Draw the generated maze in the terminal.
-    maze.print(stdout)?;
+        self.unvisited.pop();
+        events.push_back(Event::CellBacktracked((x, y)));
 
-    Ok(maze)
+        true
+    }
 }
@@ -0,0 +1,281 @@
+use crate::generator::{Algorithm, Event, Generator, Steps};
+use crate::maze::{Maze, MAX_COLUMNS};
+use anyhow::{bail, Result};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::{BTreeMap, VecDeque};
+use std::io::Write;
+use thiserror::Error;
+
+/// Generator using Eller's algorithm, which builds the maze one row at a time.
+/// In pseudocode the algorithm looks like this:
+/// ```text
+/// row.put_cells_in_own_set()
+/// for y in 0..rows {
+///     row.randomly_join_neighbors_in_different_sets(); // all of them in the last row.
+///     row.randomly_carve_down_at_least_once_per_set(); // not in the last row.
+///     row = row.next(); // cells without a passage up get a new set.
+/// }
+/// ```
+///
+/// Only the sets of the current row are needed, so [`stream`] can write mazes of any height without holding them
+/// in memory.
+pub struct Eller;
+
+impl Generator for Eller {
+    fn name(&self) -> &'static str {
+        "eller"
+    }
+
+    fn description(&self) -> &'static str {
+        "Eller's algorithm, builds the maze row by row with memory for a single row"
+    }
+
+    fn supports_mask(&self) -> bool {
+        false
+    }
+
+    fn steps(&self, maze: Maze, seed: u64) -> Steps {
+        let search = Search {
+            rows: Rows::new(maze.columns()),
+            y: 0,
+        };
+
+        Steps::new(maze, ChaCha8Rng::seed_from_u64(seed), search)
+    }
+}
+
+/// State of Eller's algorithm on a maze.
+struct Search {
+    rows: Rows,
+    y: u16,
+}
+
+impl Algorithm for Search {
+    fn step(
+        &mut self,
+        maze: &mut Maze,
+        rng: &mut ChaCha8Rng,
+        events: &mut VecDeque<Event>,
+    ) -> bool {
+        // Stop once all rows have been generated.
+        if self.y == maze.rows() {
+            return false;
+        }
+
+        let y = self.y;
+        let row = self.rows.next(rng, y == maze.rows() - 1);
+
+        for x in 0..maze.columns() {
+            if row.right[x as usize] {
+                maze.carve((x, y), (x + 1, y));
+                events.push_back(Event::WallCarved((x, y), (x + 1, y)));
+                events.push_back(Event::SetsMerged((x, y), (x + 1, y)));
+            }
+        }
+
+        for x in 0..maze.columns() {
+            if row.down[x as usize] {
+                maze.carve((x, y), (x, y + 1));
+                events.push_back(Event::WallCarved((x, y), (x, y + 1)));
+            }
+        }
+
+        self.y += 1;
+
+        true
+    }
+}
+
+/// Passages out of the cells of a single row.
+struct Row {
+    /// Whether each cell is connected to the cell on its right.
+    right: Vec<bool>,
+    /// Whether each cell is connected to the cell below it.
+    down: Vec<bool>,
+}
+
+/// Sets of the cells in the current row, which is all that Eller's algorithm remembers.
+struct Rows {
+    sets: Vec<u64>,
+    /// Ordered by set, so the random draws per set happen in the same order for the same seed.
+    members: BTreeMap<u64, Vec<usize>>,
+    next_set: u64,
+}
+
+impl Rows {
+    /// Returns the state for the first row, with every cell in its own set.
+    fn new(columns: u16) -> Rows {
+        let mut rows = Rows {
+            sets: vec![0; columns as usize],
+            members: BTreeMap::new(),
+            next_set: 0,
+        };
+        for x in 0..columns as usize {
+            rows.new_set(x);
+        }
+        rows
+    }
+
+    /// Puts a cell in a set of its own.
+    fn new_set(&mut self, x: usize) {
+        self.sets[x] = self.next_set;
+        self.members.insert(self.next_set, vec![x]);
+        self.next_set += 1;
+    }
+
+    /// Generates the passages of the current row and moves on to the next row.
+    /// In the last row all neighbors in different sets are joined, and there are no passages down.
+    fn next(&mut self, rng: &mut ChaCha8Rng, last: bool) -> Row {
+        let columns = self.sets.len();
+        let mut right = vec![false; columns];
+        let mut down = vec![false; columns];
+
+        // Randomly join neighbors in different sets, moving the smaller set into the larger one.
+        for (x, passage) in right.iter_mut().enumerate().take(columns.saturating_sub(1)) {
+            let (set_1, set_2) = (self.sets[x], self.sets[x + 1]);
+            if set_1 == set_2 || !(last || rng.gen_bool(0.5)) {
+                continue;
+            }
+
+            *passage = true;
+
+            let (keep, merge) = match self.members[&set_1].len() >= self.members[&set_2].len() {
+                true => (set_1, set_2),
+                false => (set_2, set_1),
+            };
+            let merged = self.members.remove(&merge).unwrap();
+            for &member in &merged {
+                self.sets[member] = keep;
+            }
+            self.members.get_mut(&keep).unwrap().extend(merged);
+        }
+
+        if last {
+            return Row { right, down };
+        }
+
+        // Carve down at least once from every set.
+        for members in self.members.values() {
+            for &member in members {
+                down[member] = rng.gen_bool(0.5);
+            }
+            if !members.iter().any(|&member| down[member]) {
+                down[*members.choose(rng).unwrap()] = true;
+            }
+        }
+
+        // Cells of the next row without a passage up start in a new set.
+        self.members.clear();
+        for (x, &passage) in down.iter().enumerate() {
+            match passage {
+                true => self.members.entry(self.sets[x]).or_default().push(x),
+                false => self.new_set(x),
+            }
+        }
+
+        Row { right, down }
+    }
+}
+
+/// Writes a maze generated by Eller's algorithm to `out` in the maze file format, one row at a time.
+/// Only a single row is kept in memory, so the number of rows is not limited to the size of a [`Maze`].
+/// The maze is entered at the top left cell and exited at the bottom right cell, and written exactly as
+/// [`Maze::write`] writes the same maze generated by [`Eller`]. Fails unless there is at least 1 row and there are
+/// between 1 and [`MAX_COLUMNS`] columns.
+pub fn stream(out: &mut dyn Write, rows: u64, columns: u16, seed: u64) -> Result<()> {
+    if rows == 0 || !(1..=MAX_COLUMNS).contains(&columns) {
+        bail!(StreamError::InvalidDimensions(rows, columns));
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut state = Rows::new(columns);
+
+    // Top border with the entrance above the top left cell. A single row also has its exit in the top border, as
+    // openings prefer the top border.
+    let mut line = String::from("_");
+    for x in 0..columns {
+        line.push(if x == 0 || rows == 1 && x == columns - 1 {
+            ' '
+        } else {
+            '_'
+        });
+        line.push('_');
+    }
+    writeln!(out, "{line}")?;
+
+    for y in 0..rows {
+        let last = y == rows - 1;
+        let row = state.next(&mut rng, last);
+
+        // Every cell has a wall below it, except for passages down and the exit below the bottom right cell.
+        let exit = |x: usize| last && rows > 1 && x == columns as usize - 1;
+        let floor = |x: usize| !(row.down[x] || exit(x));
+
+        line.clear();
+        line.push('|');
+        for x in 0..columns as usize {
+            line.push(if floor(x) { '_' } else { ' ' });
+            // Passages to the right keep the underline, like walls carved by `Maze::carve`.
+            line.push(if row.right[x] { '_' } else { '|' });
+        }
+        writeln!(out, "{line}")?;
+    }
+
+    out.flush()?;
+
+    Ok(())
+}
+
+/// Errors that can occur while streaming a maze.
+#[derive(Error, Debug)]
+pub enum StreamError {
+    #[error("A maze of {0} rows and {1} columns can not be streamed, it should have at least 1 row and 1 to {max} columns.", max = MAX_COLUMNS)]
+    InvalidDimensions(u64, u16),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::NullRenderer;
+
+    /// Returns the maze streamed for the seed.
+    fn streamed(rows: u64, columns: u16, seed: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        stream(&mut out, rows, columns, seed).unwrap();
+        out
+    }
+
+    #[test]
+    fn same_seed_streams_same_maze() {
+        for seed in [0, 1, 1234] {
+            assert_eq!(streamed(40, 13, seed), streamed(40, 13, seed));
+        }
+    }
+
+    #[test]
+    fn streams_same_maze_as_steps() {
+        for (rows, columns) in [(1, 1), (1, 13), (9, 1), (9, 13)] {
+            for seed in [0, 1, 1234] {
+                let maze = Eller
+                    .generate(&mut NullRenderer, Maze::new_walled(rows, columns), seed, 0)
+                    .unwrap();
+                let mut written = Vec::new();
+                maze.write(&mut written).unwrap();
+
+                assert_eq!(
+                    String::from_utf8(streamed(rows as u64, columns, seed)).unwrap(),
+                    String::from_utf8(written).unwrap(),
+                    "{rows} rows and {columns} columns with seed {seed}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_empty_mazes() {
+        assert!(stream(&mut Vec::new(), 0, 13, 0).is_err());
+        assert!(stream(&mut Vec::new(), 9, 0, 0).is_err());
+    }
+}
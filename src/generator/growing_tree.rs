@@ -0,0 +1,204 @@
+use crate::generator::{Algorithm, Event, Generator, Steps};
+use crate::maze::{Cell, Maze};
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::{HashSet, VecDeque};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Generator using the growing tree algorithm, with a configurable strategy for picking the next cell.
+/// In pseudocode the algorithm looks like this:
+/// ```text
+/// active.push(start)
+/// while !active.is_empty() {
+///     cell = active.select(strategy); // newest is a depth-first search, random is close to Prim's.
+///     if cell.has_unvisited_neighbor() {
+///         active.push(neighbor); // neighbor is picked randomly.
+///     } else {
+///         active.remove(cell);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GrowingTree {
+    strategy: Strategy,
+}
+
+impl GrowingTree {
+    /// Returns generator picking cells with the given strategy.
+    pub fn new(strategy: Strategy) -> GrowingTree {
+        GrowingTree { strategy }
+    }
+}
+
+impl Generator for GrowingTree {
+    fn name(&self) -> &'static str {
+        "growing_tree"
+    }
+
+    fn description(&self) -> &'static str {
+        "Growing tree, picks active cells by a newest, oldest or random strategy"
+    }
+
+    fn steps(&self, maze: Maze, seed: u64) -> Steps {
+        // Initialize variables for the growing tree algorithm.
+        let search = Search {
+            strategy: self.strategy.clone(),
+            visited: HashSet::new(),
+            active: vec![maze.start()],
+        };
+
+        Steps::new(maze, ChaCha8Rng::seed_from_u64(seed), search)
+    }
+}
+
+/// Ways to pick a cell from the active cells.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Selection {
+    /// The most recently added cell.
+    Newest,
+    /// The least recently added cell.
+    Oldest,
+    /// A uniformly random cell.
+    Random,
+}
+
+/// Weighted mix of selections, written as for example `newest:75,random:25`. Weights default to 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Strategy {
+    weights: Vec<(Selection, u32)>,
+}
+
+impl Strategy {
+    /// Returns strategy that always uses the same selection.
+    pub fn new(selection: Selection) -> Strategy {
+        Strategy {
+            weights: vec![(selection, 1)],
+        }
+    }
+
+    /// Returns strategy picking each selection with a probability proportional to its weight.
+    pub fn weighted(weights: Vec<(Selection, u32)>) -> Result<Strategy, InvalidStrategy> {
+        if !weights.iter().any(|&(_, weight)| weight > 0) {
+            return Err(InvalidStrategy::NoWeight);
+        }
+        Ok(Strategy { weights })
+    }
+
+    /// Returns the index of the next cell out of the given number of active cells.
+    fn select(&self, rng: &mut ChaCha8Rng, len: usize) -> usize {
+        // Summed as u64, so large weights can not overflow the total.
+        let &(selection, _) = self
+            .weights
+            .choose_weighted(rng, |&(_, weight)| u64::from(weight))
+            .expect("strategies have a positive total weight");
+
+        match selection {
+            Selection::Newest => len - 1,
+            Selection::Oldest => 0,
+            Selection::Random => rng.gen_range(0..len),
+        }
+    }
+}
+
+impl Default for Strategy {
+    fn default() -> Strategy {
+        Strategy {
+            weights: vec![(Selection::Newest, 75), (Selection::Random, 25)],
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum InvalidStrategy {
+    #[error("Unknown selection '{0}', expected newest, oldest or random.")]
+    UnknownSelection(String),
+    #[error("Invalid weight '{0}', expected a non-negative integer.")]
+    InvalidWeight(String),
+    #[error("The weights of a strategy must not all be zero.")]
+    NoWeight,
+}
+
+impl FromStr for Strategy {
+    type Err = InvalidStrategy;
+
+    fn from_str(value: &str) -> Result<Strategy, InvalidStrategy> {
+        let weights = value
+            .split(',')
+            .map(|part| {
+                let (name, weight) = part.split_once(':').unwrap_or((part, "1"));
+                let selection = match name.trim() {
+                    "newest" => Selection::Newest,
+                    "oldest" => Selection::Oldest,
+                    "random" => Selection::Random,
+                    name => return Err(InvalidStrategy::UnknownSelection(name.to_string())),
+                };
+                let weight = weight
+                    .trim()
+                    .parse()
+                    .map_err(|_| InvalidStrategy::InvalidWeight(weight.to_string()))?;
+                Ok((selection, weight))
+            })
+            .try_collect()?;
+
+        Strategy::weighted(weights)
+    }
+}
+
+/// State of the growing tree algorithm.
+struct Search {
+    strategy: Strategy,
+    visited: HashSet<Cell>,
+    active: Vec<Cell>,
+}
+
+impl Algorithm for Search {
+    fn step(
+        &mut self,
+        maze: &mut Maze,
+        rng: &mut ChaCha8Rng,
+        events: &mut VecDeque<Event>,
+    ) -> bool {
+        // Stop once there are no active cells left.
+        if self.active.is_empty() {
+            return false;
+        }
+
+        let index = self.strategy.select(rng, self.active.len());
+        let cell = self.active[index];
+
+        // The start cell is entered without coming from anywhere.
+        if self.visited.insert(cell) {
+            events.push_back(Event::CellEntered { cell, from: None });
+        }
+
+        // Pick a random unvisited neighbor.
+        let neighbors = maze
+            .neighbors(cell)
+            .filter(|neighbor| !self.visited.contains(neighbor))
+            .collect_vec();
+
+        if let Some(&neighbor) = neighbors.choose(rng) {
+            self.visited.insert(neighbor);
+            self.active.push(neighbor);
+
+            maze.carve(cell, neighbor);
+
+            events.push_back(Event::WallCarved(cell, neighbor));
+            events.push_back(Event::CellEntered {
+                cell: neighbor,
+                from: Some(cell),
+            });
+
+            return true;
+        }
+
+        // No more neighbors to visit at this cell, so remove it.
+        self.active.remove(index);
+        events.push_back(Event::CellBacktracked(cell));
+
+        true
+    }
+}
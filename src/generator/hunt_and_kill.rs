@@ -0,0 +1,157 @@
+use crate::generator::{Algorithm, Event, Generator, Steps};
+use crate::maze::{Cell, Maze};
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::{HashSet, VecDeque};
+
+/// Generator using the hunt-and-kill algorithm, which walks randomly instead of keeping a stack.
+/// In pseudocode the algorithm looks like this:
+/// ```text
+/// cell = start
+/// loop {
+///     if cell.has_unvisited_neighbor() {
+///         maze.carve(cell, neighbor); // neighbor is picked randomly.
+///         cell = neighbor;
+///     } else if let Some(unvisited) = maze.scan_rows_for_unvisited_cell_next_to_visited_cell() {
+///         maze.carve(unvisited, unvisited.random_visited_neighbor());
+///         cell = unvisited;
+///     } else {
+///         break;
+///     }
+/// }
+/// ```
+pub struct HuntAndKill;
+
+impl Generator for HuntAndKill {
+    fn name(&self) -> &'static str {
+        "hunt_and_kill"
+    }
+
+    fn description(&self) -> &'static str {
+        "Hunt-and-kill, random walks that restart from a row by row scan, no stack"
+    }
+
+    fn steps(&self, maze: Maze, seed: u64) -> Steps {
+        // Initialize variables for the hunt-and-kill algorithm.
+        let search = Search {
+            visited: HashSet::new(),
+            mode: Mode::Walk(maze.start()),
+            first: 0,
+        };
+
+        Steps::new(maze, ChaCha8Rng::seed_from_u64(seed), search)
+    }
+}
+
+/// What the hunt-and-kill algorithm is doing.
+enum Mode {
+    /// Randomly walking from the cell.
+    Walk(Cell),
+    /// Scanning the cell at the index, counted row by row, after scanning the previous cell.
+    Hunt { index: u32, previous: Option<Cell> },
+}
+
+/// State of the hunt-and-kill algorithm.
+struct Search {
+    visited: HashSet<Cell>,
+    mode: Mode,
+    /// Index of the first cell that might not be visited, counted row by row.
+    first: u32,
+}
+
+impl Algorithm for Search {
+    fn step(
+        &mut self,
+        maze: &mut Maze,
+        rng: &mut ChaCha8Rng,
+        events: &mut VecDeque<Event>,
+    ) -> bool {
+        let columns = maze.columns() as u32;
+        let total = maze.rows() as u32 * columns;
+        let cell_at = |index: u32| ((index % columns) as u16, (index / columns) as u16);
+        // Cells excluded by a mask are skipped like visited cells.
+        let skipped = |visited: &HashSet<Cell>, index: u32| {
+            let cell = cell_at(index);
+            visited.contains(&cell) || !maze.contains(cell)
+        };
+
+        match self.mode {
+            Mode::Walk(cell) => {
+                // The walk starts by visiting the start.
+                if self.visited.insert(cell) {
+                    events.push_back(Event::CellEntered { cell, from: None });
+                }
+
+                // Pick a random unvisited neighbor.
+                let neighbors = maze
+                    .neighbors(cell)
+                    .filter(|neighbor| !self.visited.contains(neighbor))
+                    .collect_vec();
+
+                if let Some(&neighbor) = neighbors.choose(rng) {
+                    self.visited.insert(neighbor);
+                    maze.carve(cell, neighbor);
+
+                    events.push_back(Event::WallCarved(cell, neighbor));
+                    events.push_back(Event::CellEntered {
+                        cell: neighbor,
+                        from: Some(cell),
+                    });
+
+                    self.mode = Mode::Walk(neighbor);
+                    return true;
+                }
+
+                // The walk is stuck, so start hunting from the first cell that might not be visited.
+                while self.first < total && skipped(&self.visited, self.first) {
+                    self.first += 1;
+                }
+                self.mode = Mode::Hunt {
+                    index: self.first,
+                    previous: None,
+                };
+            }
+            Mode::Hunt { index, previous } => {
+                // Skip visited cells, as they can not continue the walk.
+                let Some(index) = (index..total).find(|&i| !skipped(&self.visited, i)) else {
+                    // Stop once every cell has been visited.
+                    if let Some(previous) = previous {
+                        events.push_back(Event::WalkerStopped(previous));
+                    }
+                    return false;
+                };
+
+                let cell = cell_at(index);
+                events.push_back(Event::CellScanned { cell, previous });
+
+                // Continue the walk from here if the cell touches a visited cell.
+                let neighbors = maze
+                    .neighbors(cell)
+                    .filter(|neighbor| self.visited.contains(neighbor))
+                    .collect_vec();
+
+                if let Some(&neighbor) = neighbors.choose(rng) {
+                    self.visited.insert(cell);
+                    maze.carve(neighbor, cell);
+
+                    events.push_back(Event::WallCarved(neighbor, cell));
+                    events.push_back(Event::CellEntered {
+                        cell,
+                        from: Some(neighbor),
+                    });
+
+                    self.mode = Mode::Walk(cell);
+                } else {
+                    self.mode = Mode::Hunt {
+                        index: index + 1,
+                        previous: Some(cell),
+                    };
+                }
+            }
+        }
+
+        true
+    }
+}
@@ -1,131 +1,87 @@
-use crate::maze::{Maze, Wall};
-use anyhow::Result;
-use crossterm::cursor::MoveTo;
-use crossterm::QueueableCommand;
+use crate::generator::{Algorithm, Event, Generator, Steps};
+use crate::maze::{Cell, Maze};
 use disjoint_sets::UnionFind;
 use itertools::Itertools;
 use rand::seq::SliceRandom;
-use spin_sleep::sleep;
-use std::io::Stdout;
-use std::time::Duration;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::VecDeque;
 
-pub fn generate(stdout: &mut Stdout, rows: u16, columns: u16, delay: u64) -> Result<Maze> {
-    if delay == 0 {
-        return generate_instant(stdout, rows, columns);
-    }
-
-    let mut maze = Maze::new_walled(rows, columns);
-
-    maze.print(stdout)?;
-
-    // Initialize kruskal algorithm.
-    let mut cells = UnionFind::new(columns as usize * rows as usize);
-
-    // Construct vector of all walls and randomize the order.
-    let horizontal_walls = (1..2 * columns).step_by(2).cartesian_product(1..rows);
-    let vertical_walls = (2..2 * columns).step_by(2).cartesian_product(1..=rows);
-    let mut walls = horizontal_walls.chain(vertical_walls).collect_vec();
-    walls.shuffle(&mut rand::thread_rng());
-
-    // Loop while we have unvisited walls.
-    while let Some((wx, wy)) = walls.pop() {
-        sleep(Duration::from_millis(delay));
-
-        let node_1;
-        let node_2;
-        let wall;
-
-        if wx % 2 == 0 {
-            wall = Wall::Vertical;
-            node_1 = ((wx - 1 - 1) / 2, wy - 1); // Node left of wall.
-            node_2 = ((wx + 1 - 1) / 2, wy - 1); // Node right of wall.
-        } else {
-            wall = Wall::Horizontal(' ');
-            node_1 = ((wx - 1) / 2, wy - 1); // Node above wall.
-            node_2 = ((wx - 1) / 2, wy + 1 - 1); // Node below wall.
-        }
-
-        // Reduce nodes to unique identifiers.
-        let id1 = node_1.1 * columns + node_1.0;
-        let id2 = node_2.1 * columns + node_2.0;
-
-        if cells.equiv(id1, id2) {
-            continue;
-        }
+/// Generator using a randomized Kruskal's algorithm.
+/// Every wall is visited in random order and opened if the cells on either side are not yet connected.
+pub struct Kruskal;
 
-        cells.union(id1, id2);
-
-        // Set cursor to wall and open it.
-        stdout.queue(MoveTo(wx, wy))?;
-        match wall {
-            Wall::Horizontal(_) => {
-                Wall::None(' ').print(stdout)?;
-                maze.set_wall(wx, wy, Wall::None(' '));
-            }
-            Wall::Vertical => {
-                Wall::Horizontal(' ').print(stdout)?;
-                maze.set_wall(wx, wy, Wall::Horizontal(' '));
-            }
-            Wall::None(_) => unreachable!(),
-        }
+impl Generator for Kruskal {
+    fn name(&self) -> &'static str {
+        "kruskal"
     }
 
-    // Set cursor after the maze.
-    stdout.queue(MoveTo(0, rows + 1))?;
+    fn description(&self) -> &'static str {
+        "Randomized Kruskal's algorithm, opens random walls between unconnected cells"
+    }
 
-    Ok(maze)
+    fn steps(&self, maze: Maze, seed: u64) -> Steps {
+        let (rows, columns) = (maze.rows(), maze.columns());
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        // Construct vector of all walls as pairs of cells and randomize the order.
+        let horizontal_walls = (0..columns)
+            .cartesian_product(1..rows)
+            .map(|(x, y)| ((x, y - 1), (x, y)));
+        let vertical_walls = (1..columns)
+            .cartesian_product(0..rows)
+            .map(|(x, y)| ((x - 1, y), (x, y)));
+        let mut walls = horizontal_walls
+            .chain(vertical_walls)
+            .filter(|&(cell, neighbor)| maze.contains(cell) && maze.contains(neighbor))
+            .collect_vec();
+        walls.shuffle(&mut rng);
+
+        // Initialize kruskal algorithm.
+        let search = Search {
+            cells: UnionFind::new(columns as usize * rows as usize),
+            walls,
+        };
+
+        Steps::new(maze, rng, search)
+    }
 }
 
-/// Stripped version of `generate()` that *only* draws at the end of generation.
-fn generate_instant(stdout: &mut Stdout, rows: u16, columns: u16) -> Result<Maze> {
-    let mut maze = Maze::new_walled(rows, columns);
-
-    // Initialize kruskal algorithm.
-    let mut cells = UnionFind::new(columns as usize * rows as usize);
-
-    // Construct vector of all walls and randomize the order.
-    let horizontal_walls = (1..2 * columns).step_by(2).cartesian_product(1..rows);
-    let vertical_walls = (2..2 * columns).step_by(2).cartesian_product(1..=rows);
-    let mut walls = horizontal_walls.chain(vertical_walls).collect_vec();
-    walls.shuffle(&mut rand::thread_rng());
-
-    // Loop while we have unvisited walls.
-    while let Some((wx, wy)) = walls.pop() {
-        let node_1;
-        let node_2;
-        let wall;
+/// State of Kruskal's algorithm.
+struct Search {
+    cells: UnionFind<u32>,
+    walls: Vec<(Cell, Cell)>,
+}
 
-        if wx % 2 == 0 {
-            wall = Wall::Vertical;
-            node_1 = ((wx - 1 - 1) / 2, wy - 1); // Node left of wall.
-            node_2 = ((wx + 1 - 1) / 2, wy - 1); // Node right of wall.
-        } else {
-            wall = Wall::Horizontal(' ');
-            node_1 = ((wx - 1) / 2, wy - 1); // Node above wall.
-            node_2 = ((wx - 1) / 2, wy + 1 - 1); // Node below wall.
-        }
+impl Algorithm for Search {
+    fn step(
+        &mut self,
+        maze: &mut Maze,
+        _rng: &mut ChaCha8Rng,
+        events: &mut VecDeque<Event>,
+    ) -> bool {
+        // Stop once all walls have been visited.
+        let Some((node_1, node_2)) = self.walls.pop() else {
+            return false;
+        };
 
         // Reduce nodes to unique identifiers.
-        let id1 = node_1.1 * columns + node_1.0;
-        let id2 = node_2.1 * columns + node_2.0;
+        let id1 = node_1.1 as u32 * maze.columns() as u32 + node_1.0 as u32;
+        let id2 = node_2.1 as u32 * maze.columns() as u32 + node_2.0 as u32;
 
         // Skip if nodes belong to the same set.
-        if cells.equiv(id1, id2) {
-            continue;
+        if self.cells.equiv(id1, id2) {
+            return true;
         }
 
-        cells.union(id1, id2);
+        self.cells.union(id1, id2);
 
-        // Update wall between the two cells.
-        match wall {
-            Wall::Horizontal(_) => maze.set_wall(wx, wy, Wall::None(' ')),
-            Wall::Vertical => maze.set_wall(wx, wy, Wall::Horizontal(' ')),
-            Wall::None(_) => unreachable!(),
-        }
-    }
+        // Open the wall between the two cells.
+        maze.carve(node_1, node_2);
 
-    // Draw the generated maze in the terminal.
-    maze.print(stdout)?;
+        events.push_back(Event::WallCarved(node_1, node_2));
+        events.push_back(Event::SetsMerged(node_1, node_2));
 
-    Ok(maze)
+        true
+    }
 }
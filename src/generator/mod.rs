@@ -1,3 +1,373 @@
+use crate::maze::{Cell, Maze, Side};
+use crate::render::{arrow, Renderer};
+use anyhow::Result;
+use rand_chacha::ChaCha8Rng;
+use spin_sleep::sleep;
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::time::Duration;
+use thiserror::Error;
+
+pub mod aldous_broder;
+pub mod binary_tree;
 pub mod breadth_first_search;
 pub mod depth_first_search;
+pub mod eller;
+pub mod growing_tree;
+pub mod hunt_and_kill;
 pub mod kruskal;
+pub mod prim;
+pub mod recursive_division;
+pub mod sidewinder;
+pub mod wilson;
+
+/// A maze generation algorithm that can be looked up by name in a [`Registry`].
+pub trait Generator {
+    /// Unique name used to select the generator, for example on the command line.
+    fn name(&self) -> &'static str;
+
+    /// Short human readable description of the generator.
+    fn description(&self) -> &'static str;
+
+    /// Returns whether the generator keeps to the cells of a masked maze. Generators that sweep the whole rectangle row
+    /// by row can not carve a connected maze around excluded cells.
+    fn supports_mask(&self) -> bool {
+        true
+    }
+
+    /// Returns the steps that generate a maze from the given walled maze, which sets the dimensions, start and goal.
+    /// The same seed always results in the same steps, as generators draw from a [`ChaCha8Rng`] seeded with it, which
+    /// unlike `rand::rngs::StdRng` is the same on every platform and rand version.
+    fn steps(&self, maze: Maze, seed: u64) -> Steps;
+
+    /// Generates and draws a maze from the given walled maze. A delay of 0 draws only the finished maze.
+    fn generate(
+        &self,
+        renderer: &mut dyn Renderer,
+        maze: Maze,
+        seed: u64,
+        delay: u64,
+    ) -> Result<Maze> {
+        let mut steps = self.steps(maze, seed);
+
+        if delay == 0 {
+            steps.by_ref().for_each(drop);
+            renderer.draw_maze(steps.maze())?;
+            return Ok(steps.into_maze());
+        }
+
+        renderer.draw_maze(steps.maze())?;
+
+        while let Some(event) = steps.next() {
+            // Wall changes are always reported together with the step that caused them.
+            if !matches!(event, Event::WallCarved(..)) {
+                sleep(Duration::from_millis(delay));
+            }
+
+            draw_event(renderer, steps.maze(), event)?;
+        }
+
+        Ok(steps.into_maze())
+    }
+}
+
+/// Collection of generators, in the order they were registered.
+pub struct Registry {
+    generators: Vec<Box<dyn Generator>>,
+}
+
+impl Registry {
+    /// Returns an empty registry. Use `Registry::default()` for one holding the built-in generators.
+    pub fn new() -> Registry {
+        Registry {
+            generators: Vec::new(),
+        }
+    }
+
+    /// Adds a generator, replacing any previously registered generator with the same name.
+    pub fn register(&mut self, generator: Box<dyn Generator>) {
+        match self
+            .generators
+            .iter()
+            .position(|g| g.name() == generator.name())
+        {
+            Some(index) => self.generators[index] = generator,
+            None => self.generators.push(generator),
+        }
+    }
+
+    /// Returns the generator with the given name.
+    pub fn get(&self, name: &str) -> Option<&dyn Generator> {
+        self.iter().find(|generator| generator.name() == name)
+    }
+
+    /// Returns an iterator over all registered generators.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Generator> {
+        self.generators.iter().map(|generator| generator.as_ref())
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Registry {
+        let mut registry = Registry::new();
+        registry.register(Box::new(depth_first_search::DepthFirstSearch));
+        registry.register(Box::new(breadth_first_search::BreadthFirstSearch));
+        registry.register(Box::new(kruskal::Kruskal));
+        registry.register(Box::new(prim::Prim));
+        registry.register(Box::new(wilson::Wilson));
+        registry.register(Box::new(aldous_broder::AldousBroder));
+        registry.register(Box::new(eller::Eller));
+        registry.register(Box::new(recursive_division::RecursiveDivision));
+        registry.register(Box::new(binary_tree::BinaryTree::default()));
+        registry.register(Box::new(sidewinder::Sidewinder::default()));
+        registry.register(Box::new(growing_tree::GrowingTree::default()));
+        registry.register(Box::new(hunt_and_kill::HuntAndKill));
+        registry
+    }
+}
+
+/// Diagonal direction that the passages of biased generators lean towards.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Bias {
+    #[default]
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl Bias {
+    /// Names of all biases, as accepted by `Bias::from_str`.
+    pub const NAMES: [&'static str; 4] = ["north-east", "north-west", "south-east", "south-west"];
+
+    /// Returns the vertical side that passages lean towards.
+    pub fn vertical(self) -> Side {
+        match self {
+            Bias::NorthEast | Bias::NorthWest => Side::Top,
+            Bias::SouthEast | Bias::SouthWest => Side::Bottom,
+        }
+    }
+
+    /// Returns the horizontal side that passages lean towards.
+    pub fn horizontal(self) -> Side {
+        match self {
+            Bias::NorthEast | Bias::SouthEast => Side::Right,
+            Bias::NorthWest | Bias::SouthWest => Side::Left,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("Unknown bias '{0}', expected north-east, north-west, south-east or south-west.")]
+pub struct UnknownBias(String);
+
+impl FromStr for Bias {
+    type Err = UnknownBias;
+
+    fn from_str(name: &str) -> Result<Bias, UnknownBias> {
+        match name {
+            "north-east" => Ok(Bias::NorthEast),
+            "north-west" => Ok(Bias::NorthWest),
+            "south-east" => Ok(Bias::SouthEast),
+            "south-west" => Ok(Bias::SouthWest),
+            name => Err(UnknownBias(name.to_string())),
+        }
+    }
+}
+
+/// Something that happened while generating a maze.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// The generator moved into a cell, coming from a neighboring cell if it is walking through the maze.
+    CellEntered { cell: Cell, from: Option<Cell> },
+    /// The wall between two neighboring cells was removed.
+    WallCarved(Cell, Cell),
+    /// A wall was added between two neighboring cells.
+    WallBuilt(Cell, Cell),
+    /// The generator retreated from a cell that has no unvisited neighbors left.
+    CellBacktracked(Cell),
+    /// A cell was added to the cells that still have to be visited.
+    FrontierAdded(Cell),
+    /// The sets of connected cells containing the two cells were merged.
+    SetsMerged(Cell, Cell),
+    /// A cell was removed from a random walk, because the walk ran into itself.
+    LoopErased(Cell),
+    /// A random walker moved to a neighboring cell, which may have been visited before.
+    WalkerMoved { from: Cell, to: Cell },
+    /// A random walker stopped at a cell.
+    WalkerStopped(Cell),
+    /// A cell was inspected while scanning for a place to continue, after the previous scanned cell.
+    CellScanned { cell: Cell, previous: Option<Cell> },
+}
+
+/// The state of a generation algorithm, advanced one step at a time by [`Steps`].
+pub trait Algorithm {
+    /// Performs the next step on the maze and pushes the events it caused.
+    /// Returns false once the maze is finished.
+    fn step(&mut self, maze: &mut Maze, rng: &mut ChaCha8Rng, events: &mut VecDeque<Event>)
+        -> bool;
+}
+
+/// Iterator over the events of a running generator. The maze is updated as the events are yielded.
+pub struct Steps {
+    maze: Maze,
+    rng: ChaCha8Rng,
+    algorithm: Box<dyn Algorithm>,
+    events: VecDeque<Event>,
+    finished: bool,
+}
+
+impl Steps {
+    /// Returns steps that run the algorithm on the given maze.
+    pub fn new(maze: Maze, rng: ChaCha8Rng, algorithm: impl Algorithm + 'static) -> Steps {
+        Steps {
+            maze,
+            rng,
+            algorithm: Box::new(algorithm),
+            events: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    /// Returns the maze in its current state.
+    pub fn maze(&self) -> &Maze {
+        &self.maze
+    }
+
+    /// Returns the maze in its current state, which is finished once the iterator is exhausted.
+    pub fn into_maze(self) -> Maze {
+        self.maze
+    }
+}
+
+impl Iterator for Steps {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Some(event);
+            }
+
+            if self.finished {
+                return None;
+            }
+
+            self.finished = !self
+                .algorithm
+                .step(&mut self.maze, &mut self.rng, &mut self.events);
+        }
+    }
+}
+
+/// Draws the changes caused by an event, with the maze already updated.
+fn draw_event(renderer: &mut dyn Renderer, maze: &Maze, event: Event) -> Result<()> {
+    match event {
+        Event::CellEntered { cell, from } => {
+            renderer.draw_cell(maze, cell)?;
+
+            // Print arrow pointing to the entered cell in the previous cell.
+            if let Some(from) = from {
+                renderer.mark_cell(maze, from, arrow(from, cell))?;
+            }
+        }
+        Event::WallCarved(cell, neighbor) | Event::WallBuilt(cell, neighbor) => {
+            renderer.draw_wall_between(maze, cell, neighbor)?
+        }
+        Event::CellBacktracked(cell) | Event::LoopErased(cell) | Event::WalkerStopped(cell) => {
+            renderer.draw_cell(maze, cell)?
+        }
+        Event::FrontierAdded(cell) => renderer.mark_cell(maze, cell, '·')?,
+        Event::SetsMerged(..) => {}
+        Event::WalkerMoved { from, to } => {
+            renderer.draw_cell(maze, from)?;
+            renderer.mark_cell(maze, to, '●')?;
+        }
+        Event::CellScanned { cell, previous } => {
+            if let Some(previous) = previous {
+                renderer.draw_cell(maze, previous)?;
+            }
+            renderer.mark_cell(maze, cell, '○')?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::{GridRenderer, NullRenderer};
+    use crate::Mask;
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+
+    /// Returns the frame of the maze the generator carves for the seed.
+    fn generate(generator: &dyn Generator, seed: u64) -> String {
+        let mut renderer = GridRenderer::new();
+        generator
+            .generate(&mut renderer, Maze::new_walled(9, 13), seed, 0)
+            .unwrap();
+        renderer.to_string()
+    }
+
+    #[test]
+    fn same_seed_generates_same_maze() {
+        for generator in Registry::default().iter() {
+            for seed in [0, 1, 1234] {
+                assert_eq!(
+                    generate(generator, seed),
+                    generate(generator, seed),
+                    "{} generated different mazes for seed {seed}",
+                    generator.name()
+                );
+            }
+        }
+    }
+
+    /// Asserts that every cell can be reached from the start without any loops, which holds when there is one passage
+    /// less than there are cells.
+    fn assert_perfect(maze: &Maze, name: &str) {
+        let mut reached = HashSet::from([maze.start()]);
+        let mut queue = VecDeque::from([maze.start()]);
+        while let Some(cell) = queue.pop_front() {
+            for neighbor in maze.open_neighbors(cell) {
+                if reached.insert(neighbor) {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        let passages = reached
+            .iter()
+            .map(|&cell| maze.open_neighbors(cell).count())
+            .sum::<usize>()
+            / 2;
+
+        let cells = maze.cells().count();
+        assert_eq!(reached.len(), cells, "{name} left cells unreachable");
+        assert_eq!(passages, cells - 1, "{name} carved loops");
+    }
+
+    #[test]
+    fn generates_perfect_mazes() {
+        for generator in Registry::default().iter() {
+            for (rows, columns) in [(1, 1), (1, 13), (9, 1), (9, 13)] {
+                let maze = generator
+                    .generate(&mut NullRenderer, Maze::new_walled(rows, columns), 7, 0)
+                    .unwrap();
+                assert_perfect(&maze, generator.name());
+            }
+        }
+
+        let heart = Mask::from_path(PathBuf::from("examples/heart.mask")).unwrap();
+        for generator in Registry::default()
+            .iter()
+            .filter(|generator| generator.supports_mask())
+        {
+            let maze = generator
+                .generate(&mut NullRenderer, Maze::new_masked(heart.clone()), 7, 0)
+                .unwrap();
+            assert_perfect(&maze, generator.name());
+        }
+    }
+}
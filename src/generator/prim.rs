@@ -0,0 +1,91 @@
+use crate::generator::{Algorithm, Event, Generator, Steps};
+use crate::maze::{Cell, Maze};
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::{HashSet, VecDeque};
+
+/// Generator using a randomized Prim's algorithm.
+/// In pseudocode the algorithm looks like this:
+/// ```text
+/// frontier.push(start)
+/// while !frontier.is_empty() {
+///     cell = frontier.remove_random();
+///     maze.carve(cell, cell.random_visited_neighbor());
+///     frontier.extend(cell.unvisited_neighbors());
+/// }
+/// ```
+pub struct Prim;
+
+impl Generator for Prim {
+    fn name(&self) -> &'static str {
+        "prim"
+    }
+
+    fn description(&self) -> &'static str {
+        "Randomized Prim's algorithm, grows from a frontier into short branchy dead ends"
+    }
+
+    fn steps(&self, maze: Maze, seed: u64) -> Steps {
+        // Initialize variables for Prim's algorithm.
+        let search = Search {
+            visited: HashSet::new(),
+            seen: HashSet::from([maze.start()]),
+            frontier: vec![maze.start()],
+        };
+
+        Steps::new(maze, ChaCha8Rng::seed_from_u64(seed), search)
+    }
+}
+
+/// State of Prim's algorithm.
+struct Search {
+    visited: HashSet<Cell>,
+    /// Cells that have been visited or added to the frontier.
+    seen: HashSet<Cell>,
+    frontier: Vec<Cell>,
+}
+
+impl Algorithm for Search {
+    fn step(
+        &mut self,
+        maze: &mut Maze,
+        rng: &mut ChaCha8Rng,
+        events: &mut VecDeque<Event>,
+    ) -> bool {
+        // Stop once the frontier is empty.
+        if self.frontier.is_empty() {
+            return false;
+        }
+
+        // Take a random cell from the frontier.
+        let cell = self
+            .frontier
+            .swap_remove(rng.gen_range(0..self.frontier.len()));
+        self.visited.insert(cell);
+
+        // Connect it to a random visited neighbor, which every cell except the start has.
+        let neighbors = maze
+            .neighbors(cell)
+            .filter(|neighbor| self.visited.contains(neighbor))
+            .collect_vec();
+
+        if let Some(&neighbor) = neighbors.choose(rng) {
+            maze.carve(neighbor, cell);
+            events.push_back(Event::WallCarved(neighbor, cell));
+        }
+
+        events.push_back(Event::CellEntered { cell, from: None });
+
+        // Grow the frontier with the neighbors that have not been seen yet.
+        for neighbor in maze.neighbors(cell).collect_vec() {
+            if self.seen.insert(neighbor) {
+                self.frontier.push(neighbor);
+                events.push_back(Event::FrontierAdded(neighbor));
+            }
+        }
+
+        true
+    }
+}
@@ -0,0 +1,145 @@
+use crate::generator::{Algorithm, Event, Generator, Steps};
+use crate::maze::Maze;
+use itertools::Itertools;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::VecDeque;
+
+/// Generator using recursive division, which adds walls to an open field instead of carving passages.
+/// In pseudocode the algorithm looks like this:
+/// ```text
+/// stack.push(maze.open_field())
+/// while let Some(chamber) = stack.pop() {
+///     if chamber.is_splittable() {
+///         wall = chamber.random_wall_with_one_passage(); // across the shorter side.
+///         stack.push(wall.first_half());
+///         stack.push(wall.second_half());
+///     }
+/// }
+/// ```
+pub struct RecursiveDivision;
+
+impl Generator for RecursiveDivision {
+    fn name(&self) -> &'static str {
+        "recursive_division"
+    }
+
+    fn description(&self) -> &'static str {
+        "Recursive division, splits open chambers with long straight walls"
+    }
+
+    fn supports_mask(&self) -> bool {
+        false
+    }
+
+    fn steps(&self, mut maze: Maze, seed: u64) -> Steps {
+        // Start from an open field.
+        for cell in maze.cells().collect_vec() {
+            for neighbor in maze.neighbors(cell).collect_vec() {
+                maze.carve(cell, neighbor);
+            }
+        }
+
+        let search = Search {
+            chambers: vec![Chamber {
+                x: 0,
+                y: 0,
+                width: maze.columns(),
+                height: maze.rows(),
+            }],
+        };
+
+        Steps::new(maze, ChaCha8Rng::seed_from_u64(seed), search)
+    }
+}
+
+/// Rectangle of cells without walls inside it.
+#[derive(Copy, Clone, Debug)]
+struct Chamber {
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+}
+
+/// State of the recursive division.
+struct Search {
+    chambers: Vec<Chamber>,
+}
+
+impl Algorithm for Search {
+    fn step(
+        &mut self,
+        maze: &mut Maze,
+        rng: &mut ChaCha8Rng,
+        events: &mut VecDeque<Event>,
+    ) -> bool {
+        // Stop once every chamber has been split down to single cell corridors.
+        let Some(Chamber {
+            x,
+            y,
+            width,
+            height,
+        }) = self.chambers.pop()
+        else {
+            return false;
+        };
+
+        // Split across the shorter side, so chambers stay roughly square.
+        let horizontal = match (width, height) {
+            (0..=1, 0..=1) => return true,
+            (0..=1, _) => true,
+            (_, 0..=1) => false,
+            (w, h) if w != h => w < h,
+            (_, _) => rng.gen_bool(0.5),
+        };
+
+        if horizontal {
+            // Wall below row wy, with a single passage at column px.
+            let wy = rng.gen_range(y..y + height - 1);
+            let px = rng.gen_range(x..x + width);
+
+            for cx in (x..x + width).filter(|&cx| cx != px) {
+                maze.build_wall((cx, wy), (cx, wy + 1));
+                events.push_back(Event::WallBuilt((cx, wy), (cx, wy + 1)));
+            }
+
+            self.chambers.push(Chamber {
+                x,
+                y,
+                width,
+                height: wy - y + 1,
+            });
+            self.chambers.push(Chamber {
+                x,
+                y: wy + 1,
+                width,
+                height: y + height - wy - 1,
+            });
+        } else {
+            // Wall right of column wx, with a single passage at row py.
+            let wx = rng.gen_range(x..x + width - 1);
+            let py = rng.gen_range(y..y + height);
+
+            for cy in (y..y + height).filter(|&cy| cy != py) {
+                maze.build_wall((wx, cy), (wx + 1, cy));
+                events.push_back(Event::WallBuilt((wx, cy), (wx + 1, cy)));
+            }
+
+            self.chambers.push(Chamber {
+                x,
+                y,
+                width: wx - x + 1,
+                height,
+            });
+            self.chambers.push(Chamber {
+                x: wx + 1,
+                y,
+                width: x + width - wx - 1,
+                height,
+            });
+        }
+
+        true
+    }
+}
@@ -0,0 +1,118 @@
+use crate::generator::{Algorithm, Bias, Event, Generator, Steps};
+use crate::maze::{Cell, Maze, Side};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::VecDeque;
+
+/// Generator using the sidewinder algorithm, which only remembers the current run of cells in a row.
+/// In pseudocode the algorithm looks like this, for a north-east bias:
+/// ```text
+/// for row in maze.rows() {
+///     for cell in row {
+///         run.push(cell);
+///         if row.is_top() || (!cell.is_rightmost() && random_bool()) {
+///             maze.carve(cell, cell.right_neighbor());
+///         } else {
+///             maze.carve(run.random_cell(), run.random_cell().top_neighbor());
+///             run.clear();
+///         }
+///     }
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Sidewinder {
+    bias: Bias,
+}
+
+impl Sidewinder {
+    /// Returns generator leaning towards the given bias.
+    pub fn new(bias: Bias) -> Sidewinder {
+        Sidewinder { bias }
+    }
+}
+
+impl Generator for Sidewinder {
+    fn name(&self) -> &'static str {
+        "sidewinder"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sidewinder, carves runs along rows that each connect once to the previous row"
+    }
+
+    fn supports_mask(&self) -> bool {
+        false
+    }
+
+    fn steps(&self, maze: Maze, seed: u64) -> Steps {
+        let search = Search {
+            bias: self.bias,
+            index: 0,
+            run: Vec::new(),
+        };
+
+        Steps::new(maze, ChaCha8Rng::seed_from_u64(seed), search)
+    }
+}
+
+/// State of the sidewinder algorithm.
+struct Search {
+    bias: Bias,
+    /// Index of the next cell, counted row by row in the horizontal direction of the bias.
+    index: u32,
+    run: Vec<Cell>,
+}
+
+impl Algorithm for Search {
+    fn step(
+        &mut self,
+        maze: &mut Maze,
+        rng: &mut ChaCha8Rng,
+        events: &mut VecDeque<Event>,
+    ) -> bool {
+        // Stop once every cell has been handled.
+        let columns = maze.columns() as u32;
+        if self.index == maze.rows() as u32 * columns {
+            return false;
+        }
+
+        let (k, y) = ((self.index % columns) as u16, (self.index / columns) as u16);
+        let x = match self.bias.horizontal() {
+            Side::Left => maze.columns() - 1 - k,
+            _ => k,
+        };
+        let cell = (x, y);
+        self.index += 1;
+
+        events.push_back(Event::CellEntered { cell, from: None });
+        self.run.push(cell);
+
+        let vertical = maze.neighbor(cell, self.bias.vertical());
+        let horizontal = maze.neighbor(cell, self.bias.horizontal());
+
+        match (vertical, horizontal) {
+            // The row along the vertical side of the bias is a single corridor.
+            (None, Some(neighbor)) => {
+                maze.carve(cell, neighbor);
+                events.push_back(Event::WallCarved(cell, neighbor));
+            }
+            (None, None) => self.run.clear(),
+            // Randomly continue the run, unless the end of the row has been reached.
+            (Some(_), Some(neighbor)) if rng.gen_bool(0.5) => {
+                maze.carve(cell, neighbor);
+                events.push_back(Event::WallCarved(cell, neighbor));
+            }
+            // Close the run by connecting a random cell of it to the previous row.
+            (Some(_), _) => {
+                let &member = self.run.choose(rng).unwrap();
+                let neighbor = maze.neighbor(member, self.bias.vertical()).unwrap();
+                maze.carve(member, neighbor);
+                events.push_back(Event::WallCarved(member, neighbor));
+                self.run.clear();
+            }
+        }
+
+        true
+    }
+}
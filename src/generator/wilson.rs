@@ -0,0 +1,133 @@
+use crate::generator::{Algorithm, Event, Generator, Steps};
+use crate::maze::{Cell, Maze};
+use itertools::Itertools;
+use rand::seq::{IteratorRandom, SliceRandom};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Generator using Wilson's algorithm, which picks uniformly from all perfect mazes.
+/// In pseudocode the algorithm looks like this:
+/// ```text
+/// maze.add(start)
+/// while let Some(cell) = cells_not_in_maze.pop() {
+///     walk = [cell]
+///     while !maze.contains(walk.last()) {
+///         walk.push(walk.last().random_neighbor());
+///         walk.erase_loops();
+///     }
+///     maze.add(walk);
+/// }
+/// ```
+pub struct Wilson;
+
+impl Generator for Wilson {
+    fn name(&self) -> &'static str {
+        "wilson"
+    }
+
+    fn description(&self) -> &'static str {
+        "Wilson's loop-erased random walks, uniformly random perfect mazes"
+    }
+
+    fn steps(&self, maze: Maze, seed: u64) -> Steps {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        // Visit the cells that start a walk in random order.
+        let mut remaining = maze.cells().collect_vec();
+        remaining.shuffle(&mut rng);
+
+        // Initialize variables for Wilson's algorithm.
+        let search = Search {
+            start: maze.start(),
+            in_maze: HashSet::new(),
+            remaining,
+            walk: Vec::new(),
+            positions: HashMap::new(),
+        };
+
+        Steps::new(maze, rng, search)
+    }
+}
+
+/// State of Wilson's algorithm.
+struct Search {
+    start: Cell,
+    in_maze: HashSet<Cell>,
+    remaining: Vec<Cell>,
+    /// The current loop-erased random walk and the index of every cell in it.
+    walk: Vec<Cell>,
+    positions: HashMap<Cell, usize>,
+}
+
+impl Algorithm for Search {
+    fn step(
+        &mut self,
+        maze: &mut Maze,
+        rng: &mut ChaCha8Rng,
+        events: &mut VecDeque<Event>,
+    ) -> bool {
+        // The maze initially consists of only the start.
+        if self.in_maze.is_empty() {
+            self.in_maze.insert(self.start);
+            events.push_back(Event::CellEntered {
+                cell: self.start,
+                from: None,
+            });
+            return true;
+        }
+
+        // Start a new walk from a random cell outside the maze.
+        let Some(&current) = self.walk.last() else {
+            while let Some(cell) = self.remaining.pop() {
+                if !self.in_maze.contains(&cell) {
+                    self.push(cell, None, events);
+                    return true;
+                }
+            }
+
+            // Stop once every cell is part of the maze.
+            return false;
+        };
+
+        let next = maze
+            .neighbors(current)
+            .choose(rng)
+            .expect("cells in a maze with more than one cell have neighbors");
+
+        if self.in_maze.contains(&next) {
+            // The walk reached the maze, so carve it into the maze.
+            for (&cell, &neighbor) in self.walk.iter().chain([&next]).tuple_windows() {
+                maze.carve(cell, neighbor);
+                self.in_maze.insert(cell);
+                events.push_back(Event::WallCarved(cell, neighbor));
+                events.push_back(Event::CellEntered { cell, from: None });
+            }
+            self.walk.clear();
+            self.positions.clear();
+        } else if let Some(&index) = self.positions.get(&next) {
+            // The walk ran into itself, so erase the loop it made.
+            for cell in self.walk.drain(index + 1..).rev() {
+                self.positions.remove(&cell);
+                events.push_back(Event::LoopErased(cell));
+            }
+            events.push_back(Event::CellEntered {
+                cell: next,
+                from: index.checked_sub(1).map(|previous| self.walk[previous]),
+            });
+        } else {
+            self.push(next, Some(current), events);
+        }
+
+        true
+    }
+}
+
+impl Search {
+    /// Extends the walk with a cell.
+    fn push(&mut self, cell: Cell, from: Option<Cell>, events: &mut VecDeque<Event>) {
+        self.positions.insert(cell, self.walk.len());
+        self.walk.push(cell);
+        events.push_back(Event::CellEntered { cell, from });
+    }
+}
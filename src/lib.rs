@@ -0,0 +1,14 @@
+//! Generate, load, solve and draw mazes in the terminal.
+//!
+//! The binary in `main.rs` is a thin command line wrapper around this library.
+
+pub mod generator;
+pub mod mask;
+pub mod maze;
+pub mod render;
+pub mod solver;
+pub mod weights;
+
+pub use mask::Mask;
+pub use maze::{Cell, Maze, Side, Wall};
+pub use weights::Weights;
@@ -1,29 +1,35 @@
-mod generator;
-mod maze;
-mod solver;
-
-use crate::maze::Maze;
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
 use clap::{ArgGroup, Parser};
-use crossterm::cursor::{MoveTo, Show};
-use crossterm::{ExecutableCommand, QueueableCommand};
-use std::io::stdout;
+use maze_solver::generator::binary_tree::BinaryTree;
+use maze_solver::generator::growing_tree::{GrowingTree, Strategy};
+use maze_solver::generator::sidewinder::Sidewinder;
+use maze_solver::generator::Bias;
+use maze_solver::maze::{ParsingError, MAX_COLUMNS, MAX_ROWS};
+use maze_solver::render::{NullRenderer, Renderer, TerminalRenderer};
+use maze_solver::{generator, solver, Cell, Mask, Maze, Weights};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use spin_sleep::sleep;
+use std::fs::File;
+use std::io::{stdout, BufWriter, Write};
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 #[command(group(ArgGroup::new("maze_used").required(true).args(["generator", "input"])))]
 struct Args {
-    /// Number of rows to draw.
-    #[arg(default_value_t = 16)]
-    rows: u16,
+    /// Number of rows to draw. Only streamed eller mazes may exceed 65534 rows.
+    #[arg(default_value_t = 16, value_parser = clap::value_parser!(u64).range(1..))]
+    rows: u64,
 
     /// Number of columns to draw.
-    #[arg(default_value_t = 48)]
+    #[arg(default_value_t = 48, value_parser = clap::value_parser!(u16).range(1..=MAX_COLUMNS as i64))]
     columns: u16,
 
     /// Generator used.
-    #[arg(short, long, value_parser = ["depth_first_search", "breadth_first_search", "kruskal"])]
+    #[arg(short, long, value_parser = generator_parser())]
     generator: Option<String>,
 
     /// Input path used.
@@ -31,7 +37,7 @@ struct Args {
     input: Option<String>,
 
     /// Solver used. If Some, then the generator will run with a delay of 0.
-    #[arg(short, long, value_parser = ["depth_first_search", "a_star"])]
+    #[arg(short, long, value_parser = solver_parser())]
     solver: Option<String>,
 
     /// Flag to enable drawing visited cells.
@@ -41,46 +47,282 @@ struct Args {
     /// Number of milliseconds between animation.
     #[arg(short, long, default_value_t = 25)]
     delay: u64,
+
+    /// Cell to start solving from, as COLUMN,ROW. Defaults to the top left cell, or the entrance of the input.
+    #[arg(long, value_parser = parse_cell)]
+    start: Option<Cell>,
+
+    /// Cell to solve towards, as COLUMN,ROW. Defaults to the bottom right cell, or the exit of the input.
+    #[arg(long, value_parser = parse_cell)]
+    goal: Option<Cell>,
+
+    /// Seed for the generator. A random seed is used and printed if none is given.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Direction that the passages of the binary_tree and sidewinder generators lean towards.
+    #[arg(long, default_value = "north-east", value_parser = bias_parser())]
+    bias: Bias,
+
+    /// Strategy the growing_tree generator picks cells with, as a weighted mix of newest, oldest and random.
+    #[arg(long, default_value = "newest:75,random:25")]
+    strategy: Strategy,
+
+    /// Path of a text or PBM mask marking which cells exist, which replaces the rows and columns. Only generators that
+    /// keep to the mask accept it, and the maze can not be written to an output.
+    #[arg(long, conflicts_with_all = ["input", "output"])]
+    mask: Option<String>,
+
+    /// Fraction of dead ends to remove from the generated maze, between 0 and 1.
+    #[arg(long, value_name = "FRACTION", conflicts_with = "input", value_parser = parse_fraction)]
+    braid: Option<f64>,
+
+    /// Fraction of the remaining walls between cells to remove from the generated maze, between 0 and 1.
+    #[arg(long, value_name = "FRACTION", conflicts_with = "input", value_parser = parse_fraction)]
+    loops: Option<f64>,
+
+    /// Path of a file with the cost of entering every cell, as rows of whitespace separated numbers.
+    #[arg(long, conflicts_with = "noise")]
+    weights: Option<String>,
+
+    /// Gives every cell a cost between 1 and MAX from seeded noise.
+    #[arg(long, value_name = "MAX", value_parser = clap::value_parser!(u32).range(1..))]
+    noise: Option<u32>,
+
+    /// Path to write the maze to, or '-' for standard output, which turns drawing off. The eller generator streams its
+    /// rows to the output without drawing them, unless other options need the whole maze.
+    #[arg(short, long)]
+    output: Option<String>,
+}
+
+/// Accepts the names of all generators in the default registry.
+fn generator_parser() -> PossibleValuesParser {
+    PossibleValuesParser::new(
+        generator::Registry::default()
+            .iter()
+            .map(|generator| PossibleValue::new(generator.name()).help(generator.description())),
+    )
+}
+
+/// Accepts the names of all biases.
+fn bias_parser() -> impl TypedValueParser<Value = Bias> {
+    PossibleValuesParser::new(Bias::NAMES).map(|name| name.parse::<Bias>().unwrap())
+}
+
+/// Accepts the names of all solvers in the default registry.
+fn solver_parser() -> PossibleValuesParser {
+    PossibleValuesParser::new(
+        solver::Registry::default()
+            .iter()
+            .map(|solver| PossibleValue::new(solver.name()).help(solver.description())),
+    )
+}
+
+/// Parses a cell written as COLUMN,ROW.
+fn parse_cell(value: &str) -> Result<Cell, String> {
+    let (column, row) = value
+        .split_once(',')
+        .ok_or_else(|| format!("expected COLUMN,ROW, found '{value}'"))?;
+    let column = column.trim().parse().map_err(|e| format!("{e}"))?;
+    let row = row.trim().parse().map_err(|e| format!("{e}"))?;
+    Ok((column, row))
+}
+
+/// Parses a fraction between 0 and 1.
+fn parse_fraction(value: &str) -> Result<f64, String> {
+    let fraction: f64 = value.parse().map_err(|e| format!("{e}"))?;
+    if !(0.0..=1.0).contains(&fraction) {
+        return Err(format!(
+            "expected a fraction between 0 and 1, found '{value}'"
+        ));
+    }
+    Ok(fraction)
+}
+
+/// Moves the start and goal of the maze to the cells given on the command line.
+fn apply_endpoints(maze: &mut Maze, args: &Args) -> Result<()> {
+    if let Some(start) = args.start {
+        maze.set_start(start)?;
+    }
+    if let Some(goal) = args.goal {
+        maze.set_goal(goal)?;
+    }
+    Ok(())
+}
+
+/// Suggests choosing the entrance and exit when a loaded maze does not have exactly one of each, which is the only
+/// error that --start and --goal can fix.
+fn suggest_endpoints(error: anyhow::Error) -> anyhow::Error {
+    match error.downcast_ref::<ParsingError>() {
+        Some(ParsingError::WrongNumberOfOpenings(_)) => {
+            error.context("Use --start and --goal to choose the entrance and exit.")
+        }
+        _ => error,
+    }
+}
+
+/// Stream of random numbers the braid and loops passes draw from, apart from the generator's stream 0.
+const BRAID_STREAM: u64 = 1;
+
+/// Stream of random numbers the noise weights are drawn from.
+const NOISE_STREAM: u64 = 2;
+
+/// Returns the random number generator for the seed that draws from its own stream, so that no two streams or seeds
+/// share their random numbers.
+fn random_stream(seed: u64, stream: u64) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(stream);
+    rng
+}
+
+/// Gives the maze the weights loaded or generated from the command line.
+fn apply_weights(maze: &mut Maze, args: &Args, seed: u64) -> Result<()> {
+    if let Some(path) = args.weights.as_deref() {
+        maze.set_weights(Weights::from_path(PathBuf::from(path))?)?;
+    }
+    if let Some(max) = args.noise {
+        maze.set_weights(Weights::noise(
+            maze.rows(),
+            maze.columns(),
+            max,
+            &mut random_stream(seed, NOISE_STREAM),
+        ))?;
+    }
+    Ok(())
+}
+
+/// Opens the path to write a maze to, where '-' is standard output.
+fn open_output(path: &str) -> Result<Box<dyn Write>> {
+    match path {
+        "-" => Ok(Box::new(stdout())),
+        path => Ok(Box::new(BufWriter::new(File::create(path)?))),
+    }
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    let mut stdout = stdout();
+    let seed = args.seed.unwrap_or_else(rand::random);
+
+    // Eller's algorithm can write its rows directly, so the maze never has to fit in memory, unless other options
+    // need the whole maze.
+    let needs_maze = args.solver.is_some()
+        || args.start.is_some()
+        || args.goal.is_some()
+        || args.braid.is_some()
+        || args.loops.is_some()
+        || args.weights.is_some()
+        || args.noise.is_some();
+    if let (Some("eller"), Some(path), false) = (
+        args.generator.as_deref(),
+        args.output.as_deref(),
+        needs_maze,
+    ) {
+        generator::eller::stream(&mut *open_output(path)?, args.rows, args.columns, seed)?;
+        eprintln!("Seed: {seed}");
+        return Ok(());
+    }
+
+    let rows = u16::try_from(args.rows)
+        .ok()
+        .filter(|&rows| rows <= MAX_ROWS)
+        .with_context(|| {
+            format!("Number of rows exceeds {MAX_ROWS}, only -g eller -o PATH can stream more.")
+        })?;
+    // Writing the maze to standard output leaves no room for drawing it there.
+    let mut renderer: Box<dyn Renderer> = match args.output.as_deref() {
+        Some("-") => Box::new(NullRenderer),
+        _ => Box::new(TerminalRenderer::new(stdout())),
+    };
 
     let delay = if args.solver.is_some() { 0 } else { args.delay };
 
-    let mut maze = match (args.input.as_deref(), args.generator.as_deref()) {
+    let maze = match (args.input.as_deref(), args.generator.as_deref()) {
         (Some(path), _) => {
-            let maze = Maze::from_path(PathBuf::from(path))?;
-            maze.print(&mut stdout)?;
+            let mut maze = match (args.start, args.goal) {
+                (Some(start), Some(goal)) => {
+                    Maze::from_path_with_endpoints(PathBuf::from(path), start, goal)?
+                }
+                (_, _) => {
+                    let mut maze =
+                        Maze::from_path(PathBuf::from(path)).map_err(suggest_endpoints)?;
+                    apply_endpoints(&mut maze, &args)?;
+                    maze
+                }
+            };
+            apply_weights(&mut maze, &args, seed)?;
+            renderer.draw_maze(&maze)?;
             maze
         }
-        (_, Some("depth_first_search")) => {
-            generator::depth_first_search::generate(&mut stdout, args.rows, args.columns, delay)?
-        }
-        (_, Some("breadth_first_search")) => {
-            generator::breadth_first_search::generate(&mut stdout, args.rows, args.columns, delay)?
-        }
-        (_, Some("kruskal")) => {
-            generator::kruskal::generate(&mut stdout, args.rows, args.columns, delay)?
+        (_, Some(name)) => {
+            let mut maze = match args.mask.as_deref() {
+                Some(path) => Maze::new_masked(Mask::from_path(PathBuf::from(path))?),
+                None => Maze::new_walled(rows, args.columns),
+            };
+            apply_endpoints(&mut maze, &args)?;
+            apply_weights(&mut maze, &args, seed)?;
+
+            // Replace the generators that have options with ones configured from the arguments.
+            let mut generators = generator::Registry::default();
+            generators.register(Box::new(BinaryTree::new(args.bias)));
+            generators.register(Box::new(Sidewinder::new(args.bias)));
+            generators.register(Box::new(GrowingTree::new(args.strategy.clone())));
+
+            let generator = generators
+                .get(name)
+                .expect("clap only accepts registered generators");
+            if maze.mask().is_some() && !generator.supports_mask() {
+                bail!("The {name} generator can not keep to the cells of a mask.");
+            }
+            let mut maze = generator.generate(&mut *renderer, maze, seed, delay)?;
+
+            // Turn the perfect maze into an imperfect one, drawing each removed wall.
+            let mut rng = random_stream(seed, BRAID_STREAM);
+            let mut carved = Vec::new();
+            if let Some(fraction) = args.braid {
+                carved.extend(maze.braid(fraction, &mut rng));
+            }
+            if let Some(fraction) = args.loops {
+                carved.extend(maze.add_loops(fraction, &mut rng));
+            }
+            for (cell, neighbor) in carved {
+                renderer.draw_wall_between(&maze, cell, neighbor)?;
+                if delay > 0 {
+                    sleep(Duration::from_millis(delay));
+                }
+            }
+
+            maze
         }
         _ => unreachable!(),
     };
 
-    #[allow(clippy::single_match)]
-    match args.solver.as_deref() {
-        Some("depth_first_search") => {
-            solver::depth_first_search::solve(&mut stdout, &mut maze, args.delay, args.trace)?;
-        }
-        Some("a_star") => {
-            solver::a_star::solve(&mut stdout, &mut maze, args.delay, args.trace)?;
-        }
-        _ => {}
+    let solution = match args.solver.as_deref() {
+        Some(name) => Some(
+            solver::Registry::default()
+                .get(name)
+                .expect("clap only accepts registered solvers")
+                .solve(&mut *renderer, &maze, args.delay, args.trace)?,
+        ),
+        None => None,
     };
 
-    // Set cursor after the maze and show it again.
-    stdout.queue(MoveTo(0, maze.get_end().1 + 2))?;
-    stdout.execute(Show)?;
+    renderer.finish(&maze)?;
+
+    // The cost only differs from the number of steps when cells have weights.
+    match (&solution, maze.weights()) {
+        (Some(solution), _) if !solution.reached => eprintln!("The goal was not reached."),
+        (Some(solution), Some(_)) => eprintln!("Cost: {}", solution.cost),
+        (_, _) => {}
+    }
+
+    if let Some(path) = args.output.as_deref() {
+        maze.write(&mut *open_output(path)?)?;
+    }
+
+    // Print the seed so the generated maze or noise can be reproduced.
+    if args.generator.is_some() || args.noise.is_some() {
+        eprintln!("Seed: {seed}");
+    }
 
     Ok(())
 }
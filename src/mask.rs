@@ -0,0 +1,311 @@
+use crate::maze::{Cell, MAX_COLUMNS, MAX_ROWS};
+use anyhow::{bail, Result};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// The Mask struct marks which cells of the rectangle spanned by a maze exist. Excluded cells are never carved into,
+/// solved through or drawn.
+///
+/// Masks are read from text files, where every line is a row of cells. Spaces and '.' exclude a cell and any other
+/// character includes it. For example a heart:
+/// ```text
+///  XX XX
+/// XXXXXXX
+///  XXXXX
+///   XXX
+///    X
+/// ```
+///
+/// Masks can also be read from PBM bitmaps, in both the plain (P1) and raw (P4) format, where black pixels are
+/// included.
+#[derive(Clone, Debug)]
+pub struct Mask {
+    rows: u16,
+    columns: u16,
+    cells: Vec<bool>,
+}
+
+impl Mask {
+    /// Returns mask from rows of cells, where true includes the cell. Shorter rows are padded with excluded cells.
+    /// Fails unless the included cells are connected and the mask fits in a maze of [`MAX_ROWS`] rows and
+    /// [`MAX_COLUMNS`] columns.
+    pub fn new(rows: Vec<Vec<bool>>) -> Result<Mask> {
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let Some(height) = u16::try_from(rows.len())
+            .ok()
+            .filter(|&height| height <= MAX_ROWS)
+        else {
+            bail!(MaskError::TooManyRows);
+        };
+        let Some(width) = u16::try_from(width)
+            .ok()
+            .filter(|&width| width <= MAX_COLUMNS)
+        else {
+            bail!(MaskError::TooManyColumns);
+        };
+
+        let cells = rows
+            .into_iter()
+            .flat_map(|mut row| {
+                row.resize(width as usize, false);
+                row
+            })
+            .collect();
+        let mask = Mask {
+            rows: height,
+            columns: width,
+            cells,
+        };
+
+        // Every included cell has to be reachable from the first one, or generators can not carve a single maze.
+        let Some(first) = mask.cells().next() else {
+            bail!(MaskError::Empty);
+        };
+        let reached = mask.reachable(first);
+        let total = mask.cells().count();
+        if reached < total {
+            bail!(MaskError::Disconnected(total - reached, first.0, first.1));
+        }
+
+        Ok(mask)
+    }
+
+    /// Parses mask from path, as a PBM bitmap if the file starts with its magic number or as text otherwise.
+    pub fn from_path(path: PathBuf) -> Result<Mask> {
+        let bytes = fs::read(path)?;
+
+        let rows = match bytes.get(..2) {
+            Some(b"P1") => parse_plain_bitmap(&bytes[2..])?,
+            Some(b"P4") => parse_raw_bitmap(&bytes[2..])?,
+            _ => parse_text(&String::from_utf8(bytes)?),
+        };
+
+        Mask::new(rows)
+    }
+
+    /// Returns the number of cell rows.
+    pub fn rows(&self) -> u16 {
+        self.rows
+    }
+
+    /// Returns the number of cell columns.
+    pub fn columns(&self) -> u16 {
+        self.columns
+    }
+
+    /// Returns whether the cell lies inside the mask and is included.
+    pub fn includes(&self, (x, y): Cell) -> bool {
+        x < self.columns
+            && y < self.rows
+            && self.cells[y as usize * self.columns as usize + x as usize]
+    }
+
+    /// Returns all included cells, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = Cell> + '_ {
+        let columns = self.columns;
+        (0..self.rows)
+            .flat_map(move |y| (0..columns).map(move |x| (x, y)))
+            .filter(|&cell| self.includes(cell))
+    }
+
+    /// Returns the number of included cells that can be reached from the cell, including itself.
+    fn reachable(&self, cell: Cell) -> usize {
+        let mut visited = vec![false; self.cells.len()];
+        let mut queue = VecDeque::from([cell]);
+        let mut count = 0;
+
+        while let Some((x, y)) = queue.pop_front() {
+            let index = y as usize * self.columns as usize + x as usize;
+            if visited[index] {
+                continue;
+            }
+            visited[index] = true;
+            count += 1;
+
+            let neighbors = [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                .into_iter()
+                .map(|(dx, dy)| (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy)));
+            queue.extend(neighbors.filter(|&neighbor| self.includes(neighbor)));
+        }
+
+        count
+    }
+}
+
+/// Errors that can occur while reading a mask.
+#[derive(Error, Debug)]
+pub enum MaskError {
+    #[error("The mask does not include any cells.")]
+    Empty,
+    #[error("{0} cells of the mask can not be reached from cell ({1}, {2}).")]
+    Disconnected(usize, u16, u16),
+    #[error("Number of rows exceeds {max}.", max = MAX_ROWS)]
+    TooManyRows,
+    #[error("Number of columns exceeds {max}.", max = MAX_COLUMNS)]
+    TooManyColumns,
+    #[error("The bitmap header should contain a width and a height.")]
+    BadHeader,
+    #[error("Bad character '{0}' found in the bitmap.")]
+    BadPixel(char),
+    #[error("The bitmap contains fewer pixels than its header declares.")]
+    NotEnoughPixels,
+}
+
+/// Parses the rows of a text mask, ignoring trailing empty lines.
+fn parse_text(text: &str) -> Vec<Vec<bool>> {
+    let mut rows = text
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|char| !matches!(char, ' ' | '.'))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    while rows.last().is_some_and(Vec::is_empty) {
+        rows.pop();
+    }
+
+    rows
+}
+
+/// Parses the width and height of a PBM header, returning them with the bytes after the header.
+fn parse_bitmap_header(bytes: &[u8]) -> Result<(usize, usize, &[u8])> {
+    let mut rest = bytes;
+    let mut numbers = Vec::new();
+
+    while numbers.len() < 2 {
+        match rest.first() {
+            Some(b'#') => {
+                // Comments run until the end of the line.
+                let end = rest
+                    .iter()
+                    .position(|&byte| byte == b'\n')
+                    .unwrap_or(rest.len());
+                rest = &rest[end..];
+            }
+            Some(byte) if byte.is_ascii_whitespace() => rest = &rest[1..],
+            Some(byte) if byte.is_ascii_digit() => {
+                let end = rest
+                    .iter()
+                    .position(|byte| !byte.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                let number = std::str::from_utf8(&rest[..end])?.parse::<usize>()?;
+                numbers.push(number);
+                rest = &rest[end..];
+            }
+            _ => bail!(MaskError::BadHeader),
+        }
+    }
+
+    // A single whitespace character separates the header from the pixels.
+    match rest.split_first() {
+        Some((byte, pixels)) if byte.is_ascii_whitespace() => Ok((numbers[0], numbers[1], pixels)),
+        Some(_) => bail!(MaskError::BadHeader),
+        None => Ok((numbers[0], numbers[1], rest)),
+    }
+}
+
+/// Parses a plain PBM bitmap, where pixels are written as '0' or '1' characters.
+fn parse_plain_bitmap(bytes: &[u8]) -> Result<Vec<Vec<bool>>> {
+    let (width, height, pixels) = parse_bitmap_header(bytes)?;
+
+    let pixels: Vec<bool> = pixels
+        .iter()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .map(|&byte| match byte {
+            b'0' => Ok(false),
+            b'1' => Ok(true),
+            byte => Err(MaskError::BadPixel(byte as char)),
+        })
+        .collect::<Result<_, _>>()?;
+
+    if width == 0 || pixels.len() < width * height {
+        bail!(MaskError::NotEnoughPixels);
+    }
+
+    Ok(pixels
+        .chunks(width)
+        .take(height)
+        .map(<[bool]>::to_vec)
+        .collect())
+}
+
+/// Parses a raw PBM bitmap, where every row of pixels is packed into bytes, most significant bit first.
+fn parse_raw_bitmap(bytes: &[u8]) -> Result<Vec<Vec<bool>>> {
+    let (width, height, pixels) = parse_bitmap_header(bytes)?;
+    let row_length = width.div_ceil(8);
+
+    if width == 0 || pixels.len() < row_length * height {
+        bail!(MaskError::NotEnoughPixels);
+    }
+
+    Ok(pixels
+        .chunks(row_length)
+        .take(height)
+        .map(|row| {
+            (0..width)
+                .map(|x| row[x / 8] & (0x80 >> (x % 8)) != 0)
+                .collect()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the included cells of the mask, row by row.
+    fn included(mask: &Mask) -> Vec<Cell> {
+        mask.cells().collect()
+    }
+
+    #[test]
+    fn parses_plain_bitmap_with_comments() {
+        let bytes = b"\n# A plus sign.\n3 # width\n# height\n3\n010\n1 1 1\n010\n";
+
+        let mask = Mask::new(parse_plain_bitmap(bytes).unwrap()).unwrap();
+
+        assert_eq!((mask.rows(), mask.columns()), (3, 3));
+        assert_eq!(included(&mask), [(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn parses_raw_bitmap_with_partial_bytes() {
+        // Rows of 10 pixels take 2 bytes each, the last 6 bits of which are padding.
+        let mut bytes = b" 10 2\n".to_vec();
+        bytes.extend([0b1111_1111, 0b1111_1111, 0b0000_0000, 0b0111_1111]);
+
+        let mask = Mask::new(parse_raw_bitmap(&bytes).unwrap()).unwrap();
+
+        assert_eq!((mask.rows(), mask.columns()), (2, 10));
+        assert_eq!(
+            included(&mask),
+            [
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (3, 0),
+                (4, 0),
+                (5, 0),
+                (6, 0),
+                (7, 0),
+                (8, 0),
+                (9, 0),
+                (9, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_disconnected_text() {
+        let error = Mask::new(parse_text("XX.\n...\n.XX\n")).unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref(),
+            Some(MaskError::Disconnected(2, 0, 0))
+        ));
+    }
+}
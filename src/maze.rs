@@ -1,15 +1,23 @@
+use crate::mask::Mask;
+use crate::weights::Weights;
 use anyhow::{bail, Result};
-use crossterm::cursor::{Hide, MoveTo};
-use crossterm::style::Attribute::{NoUnderline, Underlined};
-use crossterm::style::Print;
-use crossterm::terminal::{Clear, ClearType};
-use crossterm::{ExecutableCommand, QueueableCommand};
 use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::Rng;
 use std::fs::File;
-use std::io::{Read, Stdout, Write};
+use std::io::{Read, Write};
 use std::path::PathBuf;
 use thiserror::Error;
 
+/// Cell coordinates as (column, row), with (0, 0) being the top left cell.
+pub type Cell = (u16, u16);
+
+/// Highest number of cell rows, so the frame is at most u16::MAX characters high.
+pub const MAX_ROWS: u16 = u16::MAX - 1;
+
+/// Highest number of cell columns, so the frame is at most u16::MAX characters wide.
+pub const MAX_COLUMNS: u16 = u16::MAX / 2;
+
 /// Walls come in three types:
 /// * Horizontal (H)
 /// * Vertical (V)
@@ -23,32 +31,6 @@ pub enum Wall {
     None(char),
 }
 
-impl Wall {
-    /// Prints wall at current cursor position.
-    pub fn print(self, stdout: &mut Stdout) -> Result<()> {
-        match self {
-            Wall::Horizontal(char) => {
-                stdout.execute(Print(format!("{Underlined}{char}{NoUnderline}")))?
-            }
-            Wall::Vertical => stdout.execute(Print('│'))?,
-            Wall::None(char) => stdout.execute(Print(char))?,
-        };
-        Ok(())
-    }
-
-    /// Prints wall at current cursor position with the given character. Panics if Wall is Vertical.
-    pub fn print_with_char(self, stdout: &mut Stdout, char: char) -> Result<()> {
-        match self {
-            Wall::Horizontal(_) => {
-                stdout.execute(Print(format!("{Underlined}{char}{NoUnderline}")))?
-            }
-            Wall::None(_) => stdout.execute(Print(char))?,
-            Wall::Vertical => unreachable!(),
-        };
-        Ok(())
-    }
-}
-
 /// The Maze struct stores the frame as a matrix of Walls. Example 3x3 matrix and its frame:
 /// ```text
 /// _ _____ [[H, N, H, H, H, H, H],
@@ -56,43 +38,147 @@ impl Wall {
 /// │ _ │ │  [V, N, H, N, V, N, V],
 /// │_│__ │  [V, H, V, H, H, N, V]]
 /// ```
-#[allow(unused)]
+///
+/// Algorithms should use the cell coordinate methods such as [`Maze::open_neighbors`] and [`Maze::carve`], which hide
+/// how walls are encoded in the frame.
+#[derive(Clone, Debug)]
 pub struct Maze {
     rows: u16,
     columns: u16,
     frame: Vec<Vec<Wall>>,
+    start: Cell,
+    goal: Cell,
+    mask: Option<Mask>,
+    weights: Option<Weights>,
+}
+
+/// Sides of a cell, or of the border of the maze.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Side {
+    /// Returns an arrow pointing out of the maze through this side.
+    pub fn arrow(self) -> char {
+        match self {
+            Side::Top => '↑',
+            Side::Bottom => '↓',
+            Side::Left => '←',
+            Side::Right => '→',
+        }
+    }
+
+    /// Returns the side reached by turning a quarter counterclockwise, when facing this side.
+    pub fn left(self) -> Side {
+        match self {
+            Side::Top => Side::Left,
+            Side::Left => Side::Bottom,
+            Side::Bottom => Side::Right,
+            Side::Right => Side::Top,
+        }
+    }
+
+    /// Returns the side reached by turning a quarter clockwise, when facing this side.
+    pub fn right(self) -> Side {
+        self.left().opposite()
+    }
+
+    /// Returns the side across the cell.
+    pub fn opposite(self) -> Side {
+        match self {
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+
+    /// Returns the column and row offset towards the neighbor on this side.
+    pub fn offset(self) -> (i16, i16) {
+        match self {
+            Side::Top => (0, -1),
+            Side::Bottom => (0, 1),
+            Side::Left => (-1, 0),
+            Side::Right => (1, 0),
+        }
+    }
 }
 
 impl Maze {
-    /// Returns maze initialized with walls around every cell.
+    /// Returns maze initialized with walls around every cell, entered at the top left cell and exited at the bottom
+    /// right cell. Panics unless the maze has between 1 and [`MAX_ROWS`] rows and between 1 and [`MAX_COLUMNS`]
+    /// columns.
     pub fn new_walled(rows: u16, columns: u16) -> Maze {
-        Maze {
+        assert!(
+            (1..=MAX_ROWS).contains(&rows) && (1..=MAX_COLUMNS).contains(&columns),
+            "a maze of {rows} rows and {columns} columns should have 1 to {MAX_ROWS} rows and 1 to {MAX_COLUMNS} columns"
+        );
+        let mut maze = Maze {
             rows,
             columns,
             frame: walled_maze(rows, columns),
-        }
+            start: (0, 0),
+            goal: (columns - 1, rows - 1),
+            mask: None,
+            weights: None,
+        };
+        maze.open_border(maze.start);
+        maze.open_border(maze.goal);
+        maze
+    }
+
+    /// Returns maze initialized with walls around every cell included by the mask, entered at the first included cell
+    /// and exited at the last included cell, row by row.
+    pub fn new_masked(mask: Mask) -> Maze {
+        let mut cells = mask.cells();
+        let start = cells.next().expect("masks include at least one cell");
+        let goal = cells.last().unwrap_or(start);
+
+        let mut maze = Maze {
+            rows: mask.rows(),
+            columns: mask.columns(),
+            frame: masked_maze(&mask),
+            start,
+            goal,
+            mask: Some(mask),
+            weights: None,
+        };
+        maze.open_border(maze.start);
+        maze.open_border(maze.goal);
+        maze
     }
 
-    /// Parses maze from path.
+    /// Parses maze from path. The gaps in the outer wall are used as the start and goal, which fails unless there is
+    /// exactly one entrance and one exit.
     pub fn from_path(path: PathBuf) -> Result<Maze> {
-        parse_maze(path)
+        parse_maze(path, None)
     }
 
-    /// Clears the terminal and prints the frame of the maze to the terminal.
-    pub fn print(&self, stdout: &mut Stdout) -> Result<()> {
-        stdout.queue(Hide)?;
-        stdout.queue(MoveTo(0, 0))?;
-        stdout.queue(Clear(ClearType::All))?;
+    /// Parses maze from path with the given start and goal, opening the border next to them if needed.
+    /// Other gaps in the outer wall are kept as they are.
+    pub fn from_path_with_endpoints(path: PathBuf, start: Cell, goal: Cell) -> Result<Maze> {
+        parse_maze(path, Some((start, goal)))
+    }
 
+    /// Writes the frame in the format read by [`Maze::from_path`].
+    pub fn write(&self, out: &mut dyn Write) -> Result<()> {
         for row in &self.frame {
-            for &wall in row {
-                wall.print(stdout)?;
-            }
-            stdout.queue(Print('\n'))?;
+            let line: String = row
+                .iter()
+                .map(|wall| match wall {
+                    Wall::Horizontal(_) => '_',
+                    Wall::Vertical => '|',
+                    Wall::None(_) => ' ',
+                })
+                .collect();
+            writeln!(out, "{line}")?;
         }
 
-        // Flush to make sure the frame is drawn.
-        stdout.flush()?;
+        out.flush()?;
 
         Ok(())
     }
@@ -107,18 +193,391 @@ impl Maze {
         self.frame[row as usize][column as usize] = cell;
     }
 
-    pub fn get_end(&self) -> (u16, u16) {
-        (self.columns - 1, self.rows - 1)
+    /// Returns the number of cell rows.
+    pub fn rows(&self) -> u16 {
+        self.rows
+    }
+
+    /// Returns the number of cell columns.
+    pub fn columns(&self) -> u16 {
+        self.columns
+    }
+
+    /// Returns the number of characters in every row of the frame.
+    pub fn frame_width(&self) -> u16 {
+        frame_position(2 * self.columns as u32 + 1, 0).0
+    }
+
+    /// Returns the mask marking which cells exist, if the maze is not a full rectangle.
+    pub fn mask(&self) -> Option<&Mask> {
+        self.mask.as_ref()
+    }
+
+    /// Returns the cost of entering every cell, if the maze has any.
+    pub fn weights(&self) -> Option<&Weights> {
+        self.weights.as_ref()
+    }
+
+    /// Sets the cost of entering every cell, shading the cells by their cost. Fails unless the weights have the same
+    /// dimensions as the maze.
+    pub fn set_weights(&mut self, weights: Weights) -> Result<()> {
+        if (weights.rows(), weights.columns()) != (self.rows, self.columns) {
+            bail!(MazeError::WrongDimensions(
+                weights.rows(),
+                weights.columns(),
+                self.rows,
+                self.columns
+            ));
+        }
+
+        for cell in self.cells().collect_vec() {
+            let (cx, cy) = cell_position(cell);
+            let wall = match self.get_wall(cx, cy) {
+                Wall::Horizontal(_) => Wall::Horizontal(weights.shade(cell)),
+                Wall::None(_) => Wall::None(weights.shade(cell)),
+                Wall::Vertical => unreachable!(),
+            };
+            self.set_wall(cx, cy, wall);
+        }
+        self.weights = Some(weights);
+
+        Ok(())
+    }
+
+    /// Returns the cost of entering the cell, which is 1 without weights.
+    pub fn cost(&self, cell: Cell) -> u32 {
+        self.weights
+            .as_ref()
+            .map_or(1, |weights| weights.cost(cell))
+    }
+
+    /// Returns the cost of following the path, entering every cell after the first one.
+    pub fn path_cost(&self, path: &[Cell]) -> u64 {
+        path.iter()
+            .skip(1)
+            .map(|&cell| self.cost(cell) as u64)
+            .sum()
+    }
+
+    /// Returns the cell solvers start from.
+    pub fn start(&self) -> Cell {
+        self.start
+    }
+
+    /// Returns the cell solvers try to reach.
+    pub fn goal(&self) -> Cell {
+        self.goal
+    }
+
+    /// Moves the start to the given cell, closing the old entrance and opening the border next to the new start.
+    pub fn set_start(&mut self, cell: Cell) -> Result<()> {
+        if !self.contains(cell) {
+            bail!(MazeError::OutOfBounds(cell.0, cell.1));
+        }
+
+        if self.start != self.goal {
+            self.close_border(self.start);
+        }
+        self.start = cell;
+        self.open_border(cell);
+
+        Ok(())
+    }
+
+    /// Moves the goal to the given cell, closing the old exit and opening the border next to the new goal.
+    pub fn set_goal(&mut self, cell: Cell) -> Result<()> {
+        if !self.contains(cell) {
+            bail!(MazeError::OutOfBounds(cell.0, cell.1));
+        }
+
+        if self.goal != self.start {
+            self.close_border(self.goal);
+        }
+        self.goal = cell;
+        self.open_border(cell);
+
+        Ok(())
+    }
+
+    /// Returns the side through which the border next to the cell is open, if any.
+    pub fn opening(&self, cell: Cell) -> Option<Side> {
+        [Side::Top, Side::Bottom, Side::Left, Side::Right]
+            .into_iter()
+            .find(|&side| self.is_open(cell, side))
+    }
+
+    /// Returns all gaps in the outer wall, scanning the top, left, right and bottom border in that order. The outer wall
+    /// of a masked maze is scanned cell by cell instead, row by row.
+    pub fn openings(&self) -> Vec<(Cell, Side)> {
+        if self.mask.is_some() {
+            return self
+                .cells()
+                .flat_map(|cell| {
+                    [Side::Top, Side::Left, Side::Right, Side::Bottom].map(|side| (cell, side))
+                })
+                .filter(|&(cell, side)| self.is_open(cell, side))
+                .collect();
+        }
+
+        let top = (0..self.columns).map(|x| ((x, 0), Side::Top));
+        let left = (0..self.rows).map(|y| ((0, y), Side::Left));
+        let right = (0..self.rows).map(|y| ((self.columns - 1, y), Side::Right));
+        let bottom = (0..self.columns).map(|x| ((x, self.rows - 1), Side::Bottom));
+
+        top.chain(left)
+            .chain(right)
+            .chain(bottom)
+            .filter(|&(cell, side)| self.is_open(cell, side))
+            .collect()
+    }
+
+    /// Returns whether the border on the given side of the cell is open.
+    fn is_open(&self, cell: Cell, side: Side) -> bool {
+        match self.border_position(cell, side) {
+            Some((bx, by)) => match (side, self.get_wall(bx, by)) {
+                (_, Wall::Vertical) | (Side::Top | Side::Bottom, Wall::Horizontal(_)) => false,
+                (_, _) => true,
+            },
+            None => false,
+        }
+    }
+
+    /// Opens the border next to a cell on the edge of the maze, preferring the top and bottom borders.
+    /// Cells that already have an opening or are not on the edge are left untouched. In masked mazes the edge also
+    /// runs along excluded cells.
+    fn open_border(&mut self, cell: Cell) {
+        if self.opening(cell).is_some() {
+            return;
+        }
+
+        let Some((side, (bx, by))) = [Side::Top, Side::Bottom, Side::Left, Side::Right]
+            .into_iter()
+            .find_map(|side| Some((side, self.border_position(cell, side)?)))
+        else {
+            return;
+        };
+
+        let wall = match (side, self.get_wall(bx, by)) {
+            (Side::Top | Side::Bottom, Wall::Horizontal(char) | Wall::None(char)) => {
+                Wall::None(char)
+            }
+            (Side::Left | Side::Right, _) => Wall::None(' '),
+            (_, Wall::Vertical) => unreachable!(),
+        };
+        self.set_wall(bx, by, wall);
+    }
+
+    /// Closes the border on every side of the cell.
+    fn close_border(&mut self, cell: Cell) {
+        for side in [Side::Top, Side::Bottom, Side::Left, Side::Right] {
+            let Some((bx, by)) = self.border_position(cell, side) else {
+                continue;
+            };
+
+            let wall = match (side, self.get_wall(bx, by)) {
+                (Side::Top | Side::Bottom, Wall::Horizontal(char) | Wall::None(char)) => {
+                    Wall::Horizontal(char)
+                }
+                (Side::Left | Side::Right, _) => Wall::Vertical,
+                (_, Wall::Vertical) => unreachable!(),
+            };
+            self.set_wall(bx, by, wall);
+        }
+    }
+
+    /// Returns the frame indices of the border on the given side of the cell, if there is no neighbor on that side.
+    fn border_position(&self, (x, y): Cell, side: Side) -> Option<(u16, u16)> {
+        if self.neighbor((x, y), side).is_some() {
+            return None;
+        }
+
+        let (cx, cy) = cell_position((x, y));
+        match side {
+            Side::Top => Some((cx, cy - 1)),
+            Side::Bottom => Some((cx, cy)),
+            Side::Left => Some((cx - 1, cy)),
+            Side::Right => Some((cx + 1, cy)),
+        }
     }
+
+    /// Returns whether the cell lies inside the maze and is not excluded by its mask.
+    pub fn contains(&self, (x, y): Cell) -> bool {
+        match &self.mask {
+            Some(mask) => mask.includes((x, y)),
+            None => x < self.columns && y < self.rows,
+        }
+    }
+
+    /// Returns all cells of the maze, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = Cell> + '_ {
+        let columns = self.columns;
+        (0..self.rows)
+            .flat_map(move |y| (0..columns).map(move |x| (x, y)))
+            .filter(|&cell| self.contains(cell))
+    }
+
+    /// Returns the cells left, right, above and below the cell that lie inside the maze, regardless of walls.
+    pub fn neighbors(&self, (x, y): Cell) -> impl Iterator<Item = Cell> + '_ {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .map(move |(dx, dy)| (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy)))
+            .filter(|&neighbor| self.contains(neighbor))
+    }
+
+    /// Returns the neighbor on the given side of the cell, if it lies inside the maze.
+    pub fn neighbor(&self, (x, y): Cell, side: Side) -> Option<Cell> {
+        let (dx, dy) = side.offset();
+        let neighbor = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+        self.contains(neighbor).then_some(neighbor)
+    }
+
+    /// Returns the neighbors of the cell that can be reached without crossing a wall.
+    pub fn open_neighbors(&self, cell: Cell) -> impl Iterator<Item = Cell> + '_ {
+        self.neighbors(cell)
+            .filter(move |&neighbor| self.has_passage(cell, neighbor))
+    }
+
+    /// Returns whether two neighboring cells are connected. Cells that are not neighbors are never connected.
+    pub fn has_passage(&self, cell: Cell, neighbor: Cell) -> bool {
+        if !self.contains(cell) || !self.contains(neighbor) || !adjacent(cell, neighbor) {
+            return false;
+        }
+
+        let (wx, wy) = wall_between(cell, neighbor);
+        match (cell.1 == neighbor.1, self.get_wall(wx, wy)) {
+            (_, Wall::Vertical) | (false, Wall::Horizontal(_)) => false,
+            (_, _) => true,
+        }
+    }
+
+    /// Removes the wall between two neighboring cells. Panics if the cells are not neighbors.
+    pub fn carve(&mut self, cell: Cell, neighbor: Cell) {
+        assert!(
+            adjacent(cell, neighbor),
+            "{cell:?} and {neighbor:?} are not neighbors"
+        );
+
+        // Open walls between cells in the same row keep the underline of the cells.
+        let (wx, wy) = wall_between(cell, neighbor);
+        let wall = match (cell.1 == neighbor.1, self.get_wall(wx, wy)) {
+            (true, _) => Wall::Horizontal(' '),
+            (false, Wall::Horizontal(char) | Wall::None(char)) => Wall::None(char),
+            (false, Wall::Vertical) => unreachable!(),
+        };
+        self.set_wall(wx, wy, wall);
+    }
+
+    /// Adds a wall between two neighboring cells. Panics if the cells are not neighbors.
+    pub fn build_wall(&mut self, cell: Cell, neighbor: Cell) {
+        assert!(
+            adjacent(cell, neighbor),
+            "{cell:?} and {neighbor:?} are not neighbors"
+        );
+
+        let (wx, wy) = wall_between(cell, neighbor);
+        let wall = match (cell.1 == neighbor.1, self.get_wall(wx, wy)) {
+            (true, _) => Wall::Vertical,
+            (false, Wall::Horizontal(char) | Wall::None(char)) => Wall::Horizontal(char),
+            (false, Wall::Vertical) => unreachable!(),
+        };
+        self.set_wall(wx, wy, wall);
+    }
+
+    /// Returns the cells that are connected to exactly one neighbor, row by row.
+    pub fn dead_ends(&self) -> Vec<Cell> {
+        self.cells()
+            .filter(|&cell| self.open_neighbors(cell).count() == 1)
+            .collect()
+    }
+
+    /// Removes the given fraction of dead ends by carving each one into a walled neighbor, preferring neighbors that
+    /// are dead ends themselves. Returns the pairs of cells that were connected.
+    pub fn braid(&mut self, fraction: f64, rng: &mut impl Rng) -> Vec<(Cell, Cell)> {
+        let mut dead_ends = self.dead_ends();
+        dead_ends.shuffle(rng);
+        let count = (fraction.clamp(0.0, 1.0) * dead_ends.len() as f64).round() as usize;
+
+        let mut carved = Vec::new();
+        for cell in dead_ends.into_iter().take(count) {
+            // An earlier carve may already have removed this dead end.
+            if self.open_neighbors(cell).count() != 1 {
+                continue;
+            }
+
+            let walled = self
+                .neighbors(cell)
+                .filter(|&neighbor| !self.has_passage(cell, neighbor))
+                .collect_vec();
+            let (dead, other): (Vec<Cell>, Vec<Cell>) = walled
+                .into_iter()
+                .partition(|&neighbor| self.open_neighbors(neighbor).count() == 1);
+
+            let candidates = if dead.is_empty() { other } else { dead };
+            if let Some(&neighbor) = candidates.choose(rng) {
+                self.carve(cell, neighbor);
+                carved.push((cell, neighbor));
+            }
+        }
+
+        carved
+    }
+
+    /// Adds loops by removing the given fraction of the walls between cells, picked at random. Returns the pairs of
+    /// cells that were connected.
+    pub fn add_loops(&mut self, fraction: f64, rng: &mut impl Rng) -> Vec<(Cell, Cell)> {
+        // Only the walls to the right and below each cell, so every wall is listed once.
+        let mut walls = Vec::new();
+        for cell in self.cells() {
+            for side in [Side::Right, Side::Bottom] {
+                match self.neighbor(cell, side) {
+                    Some(neighbor) if !self.has_passage(cell, neighbor) => {
+                        walls.push((cell, neighbor))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        walls.shuffle(rng);
+        walls.truncate((fraction.clamp(0.0, 1.0) * walls.len() as f64).round() as usize);
+
+        for &(cell, neighbor) in &walls {
+            self.carve(cell, neighbor);
+        }
+
+        walls
+    }
+}
+
+/// Returns whether two cells are next to each other.
+fn adjacent((x1, y1): Cell, (x2, y2): Cell) -> bool {
+    x1.abs_diff(x2) + y1.abs_diff(y2) == 1
+}
+
+/// Returns the frame indices of a cell.
+pub(crate) fn cell_position((x, y): Cell) -> (u16, u16) {
+    frame_position(2 * x as u32 + 1, y as u32 + 1)
+}
+
+/// Returns the frame indices of the wall between two neighboring cells.
+pub(crate) fn wall_between((x1, y1): Cell, (x2, y2): Cell) -> (u16, u16) {
+    frame_position(x1 as u32 + x2 as u32 + 1, y1.min(y2) as u32 + 1)
 }
 
-/// Returns frame for a walled maze with openings in the corners.
+/// Narrows frame indices computed in u32, which fit in u16 for every cell of a maze within [`MAX_ROWS`] and
+/// [`MAX_COLUMNS`].
+fn frame_position(column: u32, row: u32) -> (u16, u16) {
+    let narrow =
+        |index: u32| u16::try_from(index).expect("frame indices of valid mazes fit in u16");
+    (narrow(column), narrow(row))
+}
+
+/// Returns frame for a walled maze without openings.
 /// For example a 3x3 matrix and its walled frame:
 /// ```text
-/// _ _____ [[H, N, H, H, H, H, H],
+/// _______ [[H, H, H, H, H, H, H],
 /// │_│_│_│  [V, H, V, H, V, H, V],
 /// │_│_│_│  [V, H, V, H, V, H, V],
-/// │_│_│ │  [V, H, V, H, V, N, V]]
+/// │_│_│_│  [V, H, V, H, V, H, V]]
 /// ```
 fn walled_maze(rows: u16, columns: u16) -> Vec<Vec<Wall>> {
     let mut buffer = Vec::new();
@@ -137,15 +596,59 @@ fn walled_maze(rows: u16, columns: u16) -> Vec<Vec<Wall>> {
         buffer.push(row.clone());
     }
 
-    // Create openings.
-    buffer[0][1] = Wall::None(' ');
-    buffer[rows as usize][columns as usize * 2 - 1] = Wall::None(' '); // (rows, columns * 2 - 1) is lower right cell
+    buffer
+}
+
+/// Returns frame for a walled maze without openings, leaving out the cells excluded by the mask.
+/// For example a 3x3 mask with the corners excluded and its frame:
+/// ```text
+///   _     [[N, N, N, H, N, N, N],
+///  _│ │_   [N, H, V, N, V, H, N],
+/// │_ _ _│  [V, N, H, N, H, N, V],
+///   │_│    [N, N, V, H, V, N, N]]
+/// ```
+/// Walls between two excluded cells are left out, except for the underline along the top of included cells.
+fn masked_maze(mask: &Mask) -> Vec<Vec<Wall>> {
+    let included = |x: i32, y: i32| x >= 0 && y >= 0 && mask.includes((x as u16, y as u16));
+    let underline = |included: bool| match included {
+        true => Wall::Horizontal(' '),
+        false => Wall::None(' '),
+    };
+
+    let mut buffer = walled_maze(mask.rows(), mask.columns());
+
+    for (fy, row) in buffer.iter_mut().enumerate() {
+        let y = fy as i32 - 1;
+        for (fx, wall) in row.iter_mut().enumerate() {
+            let x = fx as i32 / 2;
+            *wall = match (fy, fx % 2) {
+                // The top border underlines included cells.
+                (0, 1) => underline(included(x, 0)),
+                (0, _) => underline(included(x - 1, 0) || included(x, 0)),
+                // Cells keep their floor if they or the cell below are included.
+                (_, 1) => underline(included(x, y) || included(x, y + 1)),
+                // Walls between cells are kept if either cell is included.
+                (_, _) if included(x - 1, y) || included(x, y) => Wall::Vertical,
+                (_, _) => underline(included(x - 1, y + 1) || included(x, y + 1)),
+            };
+        }
+    }
 
     buffer
 }
 
+/// Errors that can occur while changing a maze.
 #[derive(Error, Debug)]
-enum ParsingError {
+pub enum MazeError {
+    #[error("Cell ({0}, {1}) lies outside of the maze.")]
+    OutOfBounds(u16, u16),
+    #[error("Weights of {0} rows and {1} columns do not fit a maze of {2} rows and {3} columns.")]
+    WrongDimensions(u16, u16, u16, u16),
+}
+
+/// Errors that can occur while parsing a maze file.
+#[derive(Error, Debug)]
+pub enum ParsingError {
     #[error("Bad character '{2}' found at {0}:{1}.")]
     BadCharacter(usize, usize, char),
     #[error("There are not enough character rows.")]
@@ -160,16 +663,25 @@ enum ParsingError {
     TooManyRows,
     #[error("Number of columns exceeds u16::MAX (65535).")]
     TooManyColumns,
+    #[error("Found {0} gaps in the outer wall, expected exactly one entrance and one exit.")]
+    WrongNumberOfOpenings(usize),
 }
 
 /// Parses maze from path. Files should be stored as follows:
 /// * Horizontal = '_' (underscore)
 /// * Vertical = '|' (pipe)
 /// * None = ' ' (space)
-fn parse_maze(path: PathBuf) -> Result<Maze> {
+///
+/// Without endpoints, the start and goal are taken from the gaps in the outer wall.
+fn parse_maze(path: PathBuf, endpoints: Option<(Cell, Cell)>) -> Result<Maze> {
     let mut buffer = String::new();
     File::open(path)?.read_to_string(&mut buffer)?;
 
+    parse_text(&buffer, endpoints)
+}
+
+/// Parses maze from the text of a maze file, see [`parse_maze`].
+fn parse_text(buffer: &str, endpoints: Option<(Cell, Cell)>) -> Result<Maze> {
     let height = u16::try_from(buffer.lines().count()).or(Err(ParsingError::TooManyRows))?;
     let width = u16::try_from(
         buffer
@@ -207,9 +719,74 @@ fn parse_maze(path: PathBuf) -> Result<Maze> {
         })
         .try_collect()?;
 
-    Ok(Maze {
+    let mut maze = Maze {
         rows: height - 1,
         columns: (width - 1) / 2,
         frame,
-    })
+        start: (0, 0),
+        goal: (0, 0),
+        mask: None,
+        weights: None,
+    };
+
+    match endpoints {
+        Some((start, goal)) => {
+            for (x, y) in [start, goal] {
+                if !maze.contains((x, y)) {
+                    bail!(MazeError::OutOfBounds(x, y));
+                }
+                maze.open_border((x, y));
+            }
+            (maze.start, maze.goal) = (start, goal);
+        }
+        None => match maze.openings()[..] {
+            [(start, _), (goal, _)] => (maze.start, maze.goal) = (start, goal),
+            ref openings => bail!(ParsingError::WrongNumberOfOpenings(openings.len())),
+        },
+    }
+
+    Ok(maze)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_are_entered_top_left_and_exited_bottom_right() {
+        for (name, size) in [
+            ("tiny", 4),
+            ("small", 8),
+            ("medium", 16),
+            ("large", 32),
+            ("huge", 64),
+        ] {
+            let maze = Maze::from_path(format!("examples/{name}.maze").into()).unwrap();
+
+            assert_eq!(maze.start(), (0, 0), "{name}");
+            assert_eq!(maze.goal(), (size - 1, size - 1), "{name}");
+        }
+    }
+
+    #[test]
+    fn rejects_wrong_number_of_openings() {
+        let one = "_ _____\n| |   |\n|___|_|\n";
+        let three = "_ _____\n| |   |\n|___|  \n";
+
+        for (text, count) in [(one, 1), (three, 3)] {
+            let error = parse_text(text, None).unwrap_err();
+            assert!(matches!(
+                error.downcast_ref(),
+                Some(&ParsingError::WrongNumberOfOpenings(found)) if found == count
+            ));
+        }
+    }
+
+    #[test]
+    fn keeps_endpoints_when_given() {
+        let three = "_ _____\n| |   |\n|___|  \n";
+
+        let maze = parse_text(three, Some(((0, 0), (1, 0)))).unwrap();
+        assert_eq!((maze.start(), maze.goal()), ((0, 0), (1, 0)));
+    }
 }
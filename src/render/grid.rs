@@ -0,0 +1,95 @@
+use crate::maze::{Maze, Wall};
+use crate::render::Renderer;
+use anyhow::Result;
+use std::fmt::{Display, Formatter};
+
+/// Renderer that keeps the drawn frame in memory as a grid of walls.
+///
+/// The grid is displayed in the same format as maze files, except that characters drawn inside a cell replace the
+/// underscore of a horizontal wall. This makes it convenient to compare output against golden snapshots.
+#[derive(Clone, Debug, Default)]
+pub struct GridRenderer {
+    grid: Vec<Vec<Wall>>,
+}
+
+impl GridRenderer {
+    /// Returns an empty grid renderer.
+    pub fn new() -> GridRenderer {
+        GridRenderer { grid: Vec::new() }
+    }
+
+    /// Returns the wall last drawn at the given frame coordinates.
+    pub fn get(&self, column: u16, row: u16) -> Option<Wall> {
+        self.grid.get(row as usize)?.get(column as usize).copied()
+    }
+}
+
+impl Renderer for GridRenderer {
+    fn draw_maze(&mut self, maze: &Maze) -> Result<()> {
+        self.grid = (0..=maze.rows())
+            .map(|row| {
+                (0..maze.frame_width())
+                    .map(|column| maze.get_wall(column, row))
+                    .collect()
+            })
+            .collect();
+        Ok(())
+    }
+
+    fn draw_wall(&mut self, column: u16, row: u16, wall: Wall) -> Result<()> {
+        self.grid[row as usize][column as usize] = wall;
+        Ok(())
+    }
+
+    fn draw_char(&mut self, column: u16, row: u16, wall: Wall, char: char) -> Result<()> {
+        self.grid[row as usize][column as usize] = match wall {
+            Wall::Horizontal(_) => Wall::Horizontal(char),
+            Wall::None(_) => Wall::None(char),
+            Wall::Vertical => unreachable!(),
+        };
+        Ok(())
+    }
+}
+
+impl Display for GridRenderer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in &self.grid {
+            for wall in row {
+                let char = match *wall {
+                    Wall::Horizontal(' ') => '_',
+                    Wall::Horizontal(char) => char,
+                    Wall::Vertical => '|',
+                    Wall::None(char) => char,
+                };
+                write!(f, "{char}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver;
+
+    #[test]
+    fn draws_solved_maze() {
+        let maze = Maze::from_path("examples/tiny.maze".into()).unwrap();
+        let mut renderer = GridRenderer::new();
+        renderer.draw_maze(&maze).unwrap();
+        solver::depth_first_search::solve(&mut renderer, &maze, 0, false).unwrap();
+
+        assert_eq!(
+            renderer.to_string(),
+            "\
+_ _______
+|→_→_→_↓|
+| | | _↓|
+| |___|↓|
+|_____|↓|
+"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use crate::maze::{cell_position, wall_between, Cell, Maze, Wall};
+use anyhow::Result;
+use std::cmp::Ordering;
+
+pub mod grid;
+pub mod null;
+pub mod terminal;
+
+pub use grid::GridRenderer;
+pub use null::NullRenderer;
+pub use terminal::TerminalRenderer;
+
+/// Output that generators and solvers draw to. Positions are given in frame coordinates, see [`Maze`].
+pub trait Renderer {
+    /// Clears the output and draws the whole frame of the maze.
+    fn draw_maze(&mut self, maze: &Maze) -> Result<()>;
+
+    /// Draws the wall at the given frame coordinates.
+    fn draw_wall(&mut self, column: u16, row: u16, wall: Wall) -> Result<()>;
+
+    /// Draws the wall at the given frame coordinates with the given character inside it.
+    /// Panics if the wall is Vertical, as those cannot hold a character.
+    fn draw_char(&mut self, column: u16, row: u16, wall: Wall, char: char) -> Result<()>;
+
+    /// Called after the last drawing call, so the output can be finalized.
+    fn finish(&mut self, _maze: &Maze) -> Result<()> {
+        Ok(())
+    }
+
+    /// Redraws a cell as it is stored in the maze, removing previously drawn characters.
+    fn draw_cell(&mut self, maze: &Maze, cell: Cell) -> Result<()> {
+        let (cx, cy) = cell_position(cell);
+        self.draw_wall(cx, cy, maze.get_wall(cx, cy))
+    }
+
+    /// Draws a character inside a cell, keeping the wall below it.
+    fn mark_cell(&mut self, maze: &Maze, cell: Cell, char: char) -> Result<()> {
+        let (cx, cy) = cell_position(cell);
+        self.draw_char(cx, cy, maze.get_wall(cx, cy), char)
+    }
+
+    /// Redraws the wall between two neighboring cells as it is stored in the maze.
+    fn draw_wall_between(&mut self, maze: &Maze, cell: Cell, neighbor: Cell) -> Result<()> {
+        let (wx, wy) = wall_between(cell, neighbor);
+        self.draw_wall(wx, wy, maze.get_wall(wx, wy))
+    }
+}
+
+/// Returns an arrow pointing from a cell to its neighbor.
+pub fn arrow((x, y): Cell, (nx, ny): Cell) -> char {
+    match (nx.cmp(&x), ny.cmp(&y)) {
+        (Ordering::Greater, _) => '→',
+        (Ordering::Less, _) => '←',
+        (_, Ordering::Greater) => '↓',
+        (_, Ordering::Less) => '↑',
+        (_, _) => unreachable!(),
+    }
+}
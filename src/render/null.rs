@@ -0,0 +1,21 @@
+use crate::maze::{Maze, Wall};
+use crate::render::Renderer;
+use anyhow::Result;
+
+/// Renderer that discards everything, for running generators and solvers headless.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct NullRenderer;
+
+impl Renderer for NullRenderer {
+    fn draw_maze(&mut self, _maze: &Maze) -> Result<()> {
+        Ok(())
+    }
+
+    fn draw_wall(&mut self, _column: u16, _row: u16, _wall: Wall) -> Result<()> {
+        Ok(())
+    }
+
+    fn draw_char(&mut self, _column: u16, _row: u16, _wall: Wall, _char: char) -> Result<()> {
+        Ok(())
+    }
+}
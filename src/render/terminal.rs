@@ -0,0 +1,81 @@
+use crate::maze::{Maze, Wall};
+use crate::render::Renderer;
+use anyhow::Result;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::style::Attribute::{NoUnderline, Underlined};
+use crossterm::style::Print;
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::{ExecutableCommand, QueueableCommand};
+use std::io::{Stdout, Write};
+
+/// Renderer that draws to a terminal using crossterm commands.
+/// Horizontal walls are drawn by underlining the character inside the cell.
+pub struct TerminalRenderer<W: Write = Stdout> {
+    out: W,
+}
+
+impl<W: Write> TerminalRenderer<W> {
+    /// Returns renderer writing to the given terminal, usually `std::io::stdout()`.
+    pub fn new(out: W) -> TerminalRenderer<W> {
+        TerminalRenderer { out }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// Prints wall at current cursor position.
+    fn print(&mut self, wall: Wall) -> Result<()> {
+        match wall {
+            Wall::Horizontal(char) => self
+                .out
+                .execute(Print(format!("{Underlined}{char}{NoUnderline}")))?,
+            Wall::Vertical => self.out.execute(Print('│'))?,
+            Wall::None(char) => self.out.execute(Print(char))?,
+        };
+        Ok(())
+    }
+}
+
+impl<W: Write> Renderer for TerminalRenderer<W> {
+    /// Clears the terminal and prints the frame of the maze.
+    fn draw_maze(&mut self, maze: &Maze) -> Result<()> {
+        self.out.queue(Hide)?;
+        self.out.queue(MoveTo(0, 0))?;
+        self.out.queue(Clear(ClearType::All))?;
+
+        for row in 0..=maze.rows() {
+            for column in 0..maze.frame_width() {
+                self.print(maze.get_wall(column, row))?;
+            }
+            self.out.queue(Print('\n'))?;
+        }
+
+        // Flush to make sure the frame is drawn.
+        self.out.flush()?;
+
+        Ok(())
+    }
+
+    fn draw_wall(&mut self, column: u16, row: u16, wall: Wall) -> Result<()> {
+        self.out.queue(MoveTo(column, row))?;
+        self.print(wall)
+    }
+
+    fn draw_char(&mut self, column: u16, row: u16, wall: Wall, char: char) -> Result<()> {
+        self.out.queue(MoveTo(column, row))?;
+        match wall {
+            Wall::Horizontal(_) => self.print(Wall::Horizontal(char)),
+            Wall::None(_) => self.print(Wall::None(char)),
+            Wall::Vertical => unreachable!(),
+        }
+    }
+
+    /// Sets the cursor after the maze and shows it again.
+    fn finish(&mut self, maze: &Maze) -> Result<()> {
+        self.out.queue(MoveTo(0, maze.rows() + 1))?;
+        self.out.execute(Show)?;
+        Ok(())
+    }
+}
@@ -1,72 +1,110 @@
-use crate::maze::{Maze, Wall};
+use crate::maze::Maze;
+use crate::render::Renderer;
+use crate::solver::{distance, draw_path, trace_path, Solution, Solver};
 use anyhow::Result;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use binary_heap_plus::BinaryHeap;
-use crossterm::cursor::MoveTo;
-use crossterm::QueueableCommand;
 use spin_sleep::sleep;
-use std::io::Stdout;
 use std::time::Duration;
 
-pub fn solve(stdout: &mut Stdout, maze: &mut Maze, delay: u64, trace: bool) -> Result<()> {
-    let (tx, ty) = maze.get_end();
+/// Solver expanding the cell with the lowest cost so far plus estimated cost to the goal, see [`solve`].
+pub struct AStar;
 
-    let mut visited = HashSet::new();
-    let mut unvisited = BinaryHeap::new_by(|&node_1, &node_2| {
-        distance(node_2, (tx, ty)).cmp(&distance(node_1, (tx, ty)))
-    });
-    unvisited.push((0, 0));
+impl Solver for AStar {
+    fn name(&self) -> &'static str {
+        "a_star"
+    }
+
+    fn description(&self) -> &'static str {
+        "Expands the cell with the lowest cost so far plus manhattan distance to the goal first, finds the cheapest path"
+    }
 
-    'top: while let Some((x, y)) = unvisited.pop() {
-        visited.insert((x, y));
+    fn solve(
+        &self,
+        renderer: &mut dyn Renderer,
+        maze: &Maze,
+        delay: u64,
+        trace: bool,
+    ) -> Result<Solution> {
+        solve(renderer, maze, delay, trace)
+    }
+}
+
+/// Searches the maze from the start to the goal, always expanding the cell with the lowest cost so far (g) plus
+/// estimated cost to the goal (h). The estimate is the manhattan distance times the cheapest cell cost, which never
+/// overestimates, so the path found is the cheapest one.
+pub fn solve(
+    renderer: &mut dyn Renderer,
+    maze: &Maze,
+    delay: u64,
+    trace: bool,
+) -> Result<Solution> {
+    let (tx, ty) = maze.goal();
+    let cheapest = maze.weights().map_or(1, |weights| weights.min()) as u64;
+    let estimate = |cell| distance(cell, (tx, ty)) as u64 * cheapest;
+
+    let mut visited = HashSet::new();
+    let mut order = Vec::new();
+    let mut parents = HashMap::new();
+    let mut costs = HashMap::from([(maze.start(), 0)]);
+
+    // Order by lowest g + h, preferring cells closer to the goal on ties.
+    let mut unvisited = BinaryHeap::new_by(|&(f1, h1, _), &(f2, h2, _)| (f2, h2).cmp(&(f1, h1)));
+    unvisited.push((estimate(maze.start()), estimate(maze.start()), maze.start()));
+
+    'top: while let Some((_, _, (x, y))) = unvisited.pop() {
+        // Skip cells already expanded through a cheaper path.
+        if !visited.insert((x, y)) {
+            continue;
+        }
+        order.push((x, y));
 
         // Print central dot in current cell.
         if trace {
             sleep(Duration::from_millis(delay));
-
-            // Calculate the frame indices of the current cell.
-            let (cx, cy) = (2 * x + 1, y + 1);
-
-            stdout.queue(MoveTo(cx, cy))?;
-            maze.get_wall(cx, cy).print_with_char(stdout, '·')?;
+            renderer.mark_cell(maze, (x, y), '·')?;
         }
 
         if (x, y) == (tx, ty) {
             break 'top;
         }
 
-        for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
-            let (nx, ny) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
-
-            // Skip out of bounds coordinates.
-            if !(0..=tx).contains(&nx) || !(0..=ty).contains(&ny) {
-                continue;
-            }
-
+        let cost = costs[&(x, y)];
+        for (nx, ny) in maze.open_neighbors((x, y)) {
             // Skip if current neighbor has been visited.
             if visited.contains(&(nx, ny)) {
                 continue;
             }
 
-            // Calculate the frame indices of the wall between the current cell and its neighbor.
-            let wx = x + nx + 1;
-            let wy = if dy == -1 { ny } else { y } + 1;
-
-            // Skip if wall between current cell and neighbor
-            match (dx, maze.get_wall(wx, wy)) {
-                (_, Wall::Vertical) | (0, Wall::Horizontal(_)) => continue,
-                (_, _) => {}
+            // Keep the neighbor's parent only if this path to it is cheaper than any found before.
+            let g = cost + maze.cost((nx, ny)) as u64;
+            if costs.get(&(nx, ny)).is_some_and(|&known| known <= g) {
+                continue;
             }
+            costs.insert((nx, ny), g);
+            parents.insert((nx, ny), (x, y));
 
-            unvisited.push((nx, ny));
+            let h = estimate((nx, ny));
+            unvisited.push((g + h, h, (nx, ny)));
         }
     }
 
-    Ok(())
-}
+    // Walk back from the goal to the start through the parents.
+    let reached = visited.contains(&(tx, ty));
+    let path = match reached {
+        true => trace_path(&parents, (tx, ty)),
+        false => Vec::new(),
+    };
+
+    let solution = Solution {
+        cost: maze.path_cost(&path),
+        path,
+        visited: order,
+        reached,
+    };
+
+    draw_path(renderer, maze, &solution.path, delay)?;
 
-/// Returns manhattan distance between cells.
-fn distance((x1, y1): (u16, u16), (x2, y2): (u16, u16)) -> u16 {
-    x1.abs_diff(x2) + y1.abs_diff(y2)
+    Ok(solution)
 }
@@ -0,0 +1,145 @@
+use crate::maze::{Cell, Maze};
+use crate::render::Renderer;
+use crate::solver::{draw_path, trace_path, Solution, Solver};
+use anyhow::Result;
+use std::collections::HashMap;
+
+use spin_sleep::sleep;
+use std::time::Duration;
+
+/// Solver searching from the start and the goal at the same time until both searches meet, see [`solve`].
+pub struct Bidirectional;
+
+impl Solver for Bidirectional {
+    fn name(&self) -> &'static str {
+        "bidirectional"
+    }
+
+    fn description(&self) -> &'static str {
+        "Breadth-first searches from the start and the goal that meet in the middle, finds the path with the fewest steps"
+    }
+
+    fn solve(
+        &self,
+        renderer: &mut dyn Renderer,
+        maze: &Maze,
+        delay: u64,
+        trace: bool,
+    ) -> Result<Solution> {
+        solve(renderer, maze, delay, trace)
+    }
+}
+
+/// State of one of the two breadth-first searches.
+struct Search {
+    /// Number of steps from where the search began to every cell it reached.
+    depths: HashMap<Cell, u32>,
+    parents: HashMap<Cell, Cell>,
+    layer: Vec<Cell>,
+    /// Character drawn in the cells reached by this search.
+    glyph: char,
+}
+
+impl Search {
+    fn new(cell: Cell, glyph: char) -> Search {
+        Search {
+            depths: HashMap::from([(cell, 0)]),
+            parents: HashMap::new(),
+            layer: vec![cell],
+            glyph,
+        }
+    }
+
+    /// Returns the cells from where the search began to the cell.
+    fn path_from(&self, cell: Cell) -> Vec<Cell> {
+        trace_path(&self.parents, cell)
+    }
+}
+
+/// Searches the maze breadth-first from the start and from the goal, one layer at a time, always growing the search
+/// with the smaller frontier. Both searches stop as soon as one reaches a cell of the other, so each only covers about
+/// half the distance. The joined path has the fewest steps, but ignores the weights of cells.
+pub fn solve(
+    renderer: &mut dyn Renderer,
+    maze: &Maze,
+    delay: u64,
+    trace: bool,
+) -> Result<Solution> {
+    let mut forward = Search::new(maze.start(), '○');
+    let mut backward = Search::new(maze.goal(), '●');
+    let mut order = vec![maze.start()];
+
+    // The shortest connection found as (steps, cell of the forward search, cell of the backward search).
+    let mut meeting = None;
+    if maze.start() == maze.goal() {
+        meeting = Some((0, maze.start(), maze.goal()));
+    } else {
+        order.push(maze.goal());
+    }
+
+    while meeting.is_none() && !forward.layer.is_empty() && !backward.layer.is_empty() {
+        let (near, far, is_forward) = if forward.layer.len() <= backward.layer.len() {
+            (&mut forward, &backward, true)
+        } else {
+            (&mut backward, &forward, false)
+        };
+
+        let mut next = Vec::new();
+        for &cell in &near.layer {
+            let depth = near.depths[&cell];
+            for neighbor in maze.open_neighbors(cell) {
+                // Keep the shortest connection, as cells of the other search lie at different depths.
+                if let Some(&other) = far.depths.get(&neighbor) {
+                    let steps = depth + 1 + other;
+                    if meeting.is_none_or(|(best, _, _)| steps < best) {
+                        meeting = Some(match is_forward {
+                            true => (steps, cell, neighbor),
+                            false => (steps, neighbor, cell),
+                        });
+                    }
+                    continue;
+                }
+
+                // Skip if current neighbor has been visited.
+                if near.depths.contains_key(&neighbor) {
+                    continue;
+                }
+
+                near.depths.insert(neighbor, depth + 1);
+                near.parents.insert(neighbor, cell);
+                order.push(neighbor);
+                next.push(neighbor);
+            }
+        }
+
+        // Draw the new layer of the search in its own glyph.
+        if trace {
+            sleep(Duration::from_millis(delay));
+            for &cell in &next {
+                renderer.mark_cell(maze, cell, near.glyph)?;
+            }
+        }
+
+        near.layer = next;
+    }
+
+    // Join the path from the start to the meeting with the path from the meeting to the goal.
+    let mut path = Vec::new();
+    if let Some((_, forward_cell, backward_cell)) = meeting {
+        path = forward.path_from(forward_cell);
+        if backward_cell != forward_cell {
+            path.extend(backward.path_from(backward_cell).into_iter().rev());
+        }
+    }
+
+    let solution = Solution {
+        cost: maze.path_cost(&path),
+        reached: meeting.is_some(),
+        path,
+        visited: order,
+    };
+
+    draw_path(renderer, maze, &solution.path, delay)?;
+
+    Ok(solution)
+}
@@ -0,0 +1,94 @@
+use crate::maze::Maze;
+use crate::render::Renderer;
+use crate::solver::{draw_path, trace_path, Solution, Solver};
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+use spin_sleep::sleep;
+use std::time::Duration;
+
+/// Solver expanding the cells layer by layer, nearest to the start first, see [`solve`].
+pub struct BreadthFirstSearch;
+
+impl Solver for BreadthFirstSearch {
+    fn name(&self) -> &'static str {
+        "breadth_first_search"
+    }
+
+    fn description(&self) -> &'static str {
+        "Expands all cells at the same distance from the start at once, finds the path with the fewest steps"
+    }
+
+    fn solve(
+        &self,
+        renderer: &mut dyn Renderer,
+        maze: &Maze,
+        delay: u64,
+        trace: bool,
+    ) -> Result<Solution> {
+        solve(renderer, maze, delay, trace)
+    }
+}
+
+/// Searches the maze from the start to the goal one layer of cells at a time, where every layer is one step further
+/// from the start than the previous one. The path found has the fewest steps, but ignores the weights of cells.
+pub fn solve(
+    renderer: &mut dyn Renderer,
+    maze: &Maze,
+    delay: u64,
+    trace: bool,
+) -> Result<Solution> {
+    let goal = maze.goal();
+
+    let mut visited = HashSet::from([maze.start()]);
+    let mut order = vec![maze.start()];
+    let mut parents = HashMap::new();
+    let mut layer = vec![maze.start()];
+
+    while !layer.is_empty() && !visited.contains(&goal) {
+        let mut next = Vec::new();
+        for &(x, y) in &layer {
+            for neighbor in maze.open_neighbors((x, y)) {
+                // Skip if current neighbor has been visited.
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+
+                order.push(neighbor);
+                parents.insert(neighbor, (x, y));
+                next.push(neighbor);
+            }
+        }
+
+        // Draw the wavefront as rings, with the cells behind it as dots.
+        if trace {
+            sleep(Duration::from_millis(delay));
+            for &cell in &layer {
+                renderer.mark_cell(maze, cell, '·')?;
+            }
+            for &cell in &next {
+                renderer.mark_cell(maze, cell, '○')?;
+            }
+        }
+
+        layer = next;
+    }
+
+    // Walk back from the goal to the start through the parents.
+    let reached = visited.contains(&goal);
+    let path = match reached {
+        true => trace_path(&parents, goal),
+        false => Vec::new(),
+    };
+
+    let solution = Solution {
+        cost: maze.path_cost(&path),
+        path,
+        visited: order,
+        reached,
+    };
+
+    draw_path(renderer, maze, &solution.path, delay)?;
+
+    Ok(solution)
+}
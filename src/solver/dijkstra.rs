@@ -0,0 +1,105 @@
+use crate::maze::{Cell, Maze};
+use crate::render::Renderer;
+use crate::solver::{draw_path, trace_path, Solution, Solver};
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+use binary_heap_plus::BinaryHeap;
+use spin_sleep::sleep;
+use std::time::Duration;
+
+/// Solver settling the cells in order of their cost from the start, see [`solve`].
+pub struct Dijkstra;
+
+impl Solver for Dijkstra {
+    fn name(&self) -> &'static str {
+        "dijkstra"
+    }
+
+    fn description(&self) -> &'static str {
+        "Uniform-cost search, expands the cell that is cheapest to reach first, finds the cheapest path"
+    }
+
+    fn solve(
+        &self,
+        renderer: &mut dyn Renderer,
+        maze: &Maze,
+        delay: u64,
+        trace: bool,
+    ) -> Result<Solution> {
+        solve(renderer, maze, delay, trace)
+    }
+}
+
+/// Searches the maze from the start to the goal, always settling the cell that is cheapest to reach from the start.
+/// Once the goal is settled, no cheaper path to it exists. The total cost is stored in the solution.
+pub fn solve(
+    renderer: &mut dyn Renderer,
+    maze: &Maze,
+    delay: u64,
+    trace: bool,
+) -> Result<Solution> {
+    let goal = maze.goal();
+
+    let mut settled = HashSet::new();
+    let mut order = Vec::new();
+    let mut parents = HashMap::new();
+    let mut costs = HashMap::from([(maze.start(), 0)]);
+    let mut unsettled =
+        BinaryHeap::new_by(|&(cost_1, _): &(u64, Cell), &(cost_2, _): &(u64, Cell)| {
+            cost_2.cmp(&cost_1)
+        });
+    unsettled.push((0, maze.start()));
+
+    'top: while let Some((cost, (x, y))) = unsettled.pop() {
+        // Skip cells already settled through a cheaper path.
+        if !settled.insert((x, y)) {
+            continue;
+        }
+        order.push((x, y));
+
+        // Print central dot in settled cell.
+        if trace {
+            sleep(Duration::from_millis(delay));
+            renderer.mark_cell(maze, (x, y), '·')?;
+        }
+
+        if (x, y) == goal {
+            break 'top;
+        }
+
+        for (nx, ny) in maze.open_neighbors((x, y)) {
+            // Skip if current neighbor has been settled.
+            if settled.contains(&(nx, ny)) {
+                continue;
+            }
+
+            // Keep the neighbor's parent only if this path to it is cheaper than any found before.
+            let next = cost + maze.cost((nx, ny)) as u64;
+            if costs.get(&(nx, ny)).is_some_and(|&known| known <= next) {
+                continue;
+            }
+            costs.insert((nx, ny), next);
+            parents.insert((nx, ny), (x, y));
+            unsettled.push((next, (nx, ny)));
+        }
+    }
+
+    // Walk back from the goal to the start through the parents.
+    let reached = settled.contains(&goal);
+    let path = match reached {
+        true => trace_path(&parents, goal),
+        false => Vec::new(),
+    };
+
+    let solution = Solution {
+        cost: if reached { costs[&goal] } else { 0 },
+        path,
+        visited: order,
+        reached,
+    };
+
+    draw_path(renderer, maze, &solution.path, delay)?;
+
+    Ok(solution)
+}
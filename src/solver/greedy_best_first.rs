@@ -0,0 +1,96 @@
+use crate::maze::Maze;
+use crate::render::Renderer;
+use crate::solver::{distance, draw_path, trace_path, Solution, Solver};
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+use binary_heap_plus::BinaryHeap;
+use spin_sleep::sleep;
+use std::time::Duration;
+
+/// Solver always expanding the cell closest to the goal, see [`solve`].
+pub struct GreedyBestFirst;
+
+impl Solver for GreedyBestFirst {
+    fn name(&self) -> &'static str {
+        "greedy_best_first"
+    }
+
+    fn description(&self) -> &'static str {
+        "Expands the cell with the smallest manhattan distance to the goal first, fast but not always the shortest"
+    }
+
+    fn solve(
+        &self,
+        renderer: &mut dyn Renderer,
+        maze: &Maze,
+        delay: u64,
+        trace: bool,
+    ) -> Result<Solution> {
+        solve(renderer, maze, delay, trace)
+    }
+}
+
+/// Searches the maze from the start to the goal, always expanding the cell closest to the goal. The path found is
+/// not always the shortest once the maze has loops.
+pub fn solve(
+    renderer: &mut dyn Renderer,
+    maze: &Maze,
+    delay: u64,
+    trace: bool,
+) -> Result<Solution> {
+    let (tx, ty) = maze.goal();
+
+    let mut visited = HashSet::new();
+    let mut order = Vec::new();
+    let mut parents = HashMap::new();
+    let mut unvisited = BinaryHeap::new_by(|&node_1, &node_2| {
+        distance(node_2, (tx, ty)).cmp(&distance(node_1, (tx, ty)))
+    });
+    unvisited.push(maze.start());
+
+    'top: while let Some((x, y)) = unvisited.pop() {
+        if !visited.insert((x, y)) {
+            continue;
+        }
+        order.push((x, y));
+
+        // Print central dot in current cell.
+        if trace {
+            sleep(Duration::from_millis(delay));
+            renderer.mark_cell(maze, (x, y), '·')?;
+        }
+
+        if (x, y) == (tx, ty) {
+            break 'top;
+        }
+
+        for (nx, ny) in maze.open_neighbors((x, y)) {
+            // Skip if current neighbor has been visited.
+            if visited.contains(&(nx, ny)) {
+                continue;
+            }
+
+            parents.entry((nx, ny)).or_insert((x, y));
+            unvisited.push((nx, ny));
+        }
+    }
+
+    // Walk back from the goal to the start through the parents.
+    let reached = visited.contains(&(tx, ty));
+    let path = match reached {
+        true => trace_path(&parents, (tx, ty)),
+        false => Vec::new(),
+    };
+
+    let solution = Solution {
+        cost: maze.path_cost(&path),
+        path,
+        visited: order,
+        reached,
+    };
+
+    draw_path(renderer, maze, &solution.path, delay)?;
+
+    Ok(solution)
+}
@@ -1,2 +1,229 @@
+use crate::maze::{Cell, Maze};
+use crate::render::{arrow, Renderer};
+use crate::solver::wall_follower::Hand;
+use anyhow::Result;
+use itertools::Itertools;
+use spin_sleep::sleep;
+use std::collections::HashMap;
+use std::time::Duration;
+
 pub mod a_star;
+pub mod bidirectional;
+pub mod breadth_first_search;
 pub mod depth_first_search;
+pub mod dijkstra;
+pub mod greedy_best_first;
+pub mod pledge;
+pub mod wall_follower;
+
+/// Outcome of solving a maze.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Solution {
+    /// Cells from the start to the goal, both included. Empty if the goal was not reached.
+    pub path: Vec<Cell>,
+    /// Cells in the order they were first visited.
+    pub visited: Vec<Cell>,
+    /// Whether a path to the goal was found.
+    pub reached: bool,
+    /// Cost of following the path, see [`Maze::path_cost`]. Equals the number of steps without weights.
+    pub cost: u64,
+}
+
+/// A maze solving algorithm that can be looked up by name in a [`Registry`].
+pub trait Solver {
+    /// Unique name used to select the solver, for example on the command line.
+    fn name(&self) -> &'static str;
+
+    /// Short human readable description of the solver.
+    fn description(&self) -> &'static str;
+
+    /// Solves the maze and draws the path found. If `trace` is set, visited cells are drawn as well.
+    fn solve(
+        &self,
+        renderer: &mut dyn Renderer,
+        maze: &Maze,
+        delay: u64,
+        trace: bool,
+    ) -> Result<Solution>;
+}
+
+/// Collection of solvers, in the order they were registered.
+pub struct Registry {
+    solvers: Vec<Box<dyn Solver>>,
+}
+
+impl Registry {
+    /// Returns an empty registry. Use `Registry::default()` for one holding the built-in solvers.
+    pub fn new() -> Registry {
+        Registry {
+            solvers: Vec::new(),
+        }
+    }
+
+    /// Adds a solver, replacing any previously registered solver with the same name.
+    pub fn register(&mut self, solver: Box<dyn Solver>) {
+        match self.solvers.iter().position(|s| s.name() == solver.name()) {
+            Some(index) => self.solvers[index] = solver,
+            None => self.solvers.push(solver),
+        }
+    }
+
+    /// Returns the solver with the given name.
+    pub fn get(&self, name: &str) -> Option<&dyn Solver> {
+        self.iter().find(|solver| solver.name() == name)
+    }
+
+    /// Returns an iterator over all registered solvers.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Solver> {
+        self.solvers.iter().map(|solver| solver.as_ref())
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Registry {
+        let mut registry = Registry::new();
+        registry.register(Box::new(depth_first_search::DepthFirstSearch));
+        registry.register(Box::new(a_star::AStar));
+        registry.register(Box::new(greedy_best_first::GreedyBestFirst));
+        registry.register(Box::new(breadth_first_search::BreadthFirstSearch));
+        registry.register(Box::new(dijkstra::Dijkstra));
+        registry.register(Box::new(bidirectional::Bidirectional));
+        registry.register(Box::new(wall_follower::WallFollower::new(Hand::Left)));
+        registry.register(Box::new(wall_follower::WallFollower::new(Hand::Right)));
+        registry.register(Box::new(pledge::Pledge));
+        registry
+    }
+}
+
+/// Draws arrows along the path, ending with an arrow out of the exit if the last cell is on the border.
+fn draw_path(renderer: &mut dyn Renderer, maze: &Maze, path: &[Cell], delay: u64) -> Result<()> {
+    for (&cell, &next) in path.iter().tuple_windows() {
+        sleep(Duration::from_millis(delay));
+
+        // Print arrow pointing to neighbor in current cell.
+        renderer.mark_cell(maze, cell, arrow(cell, next))?;
+    }
+
+    if let Some(&last) = path.last() {
+        sleep(Duration::from_millis(delay));
+
+        // Print arrow pointing out of the maze, or a cross if the goal has no exit.
+        let dir = maze.opening(last).map_or('×', |side| side.arrow());
+        renderer.mark_cell(maze, last, dir)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the path from the start to the goal, found by walking back from the goal through the parent of every cell.
+fn trace_path(parents: &HashMap<Cell, Cell>, goal: Cell) -> Vec<Cell> {
+    let mut path = vec![goal];
+    while let Some(&parent) = parents.get(path.last().unwrap()) {
+        path.push(parent);
+    }
+    path.reverse();
+    path
+}
+
+/// Returns manhattan distance between cells.
+fn distance((x1, y1): Cell, (x2, y2): Cell) -> u16 {
+    x1.abs_diff(x2) + y1.abs_diff(y2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator;
+    use crate::render::NullRenderer;
+    use crate::Weights;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Returns a kruskal maze with loops and weighted cells, so there are paths of different costs to pick from.
+    fn looped_maze(seed: u64) -> Maze {
+        let mut maze = generator::Registry::default()
+            .get("kruskal")
+            .unwrap()
+            .generate(&mut NullRenderer, Maze::new_walled(12, 20), seed, 0)
+            .unwrap();
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        rng.set_stream(1);
+        maze.braid(1.0, &mut rng);
+        maze.add_loops(0.2, &mut rng);
+        maze.set_weights(Weights::noise(12, 20, 9, &mut rng))
+            .unwrap();
+        maze
+    }
+
+    /// Returns the solution the named solver finds for the maze.
+    fn solve(name: &str, maze: &Maze) -> Solution {
+        Registry::default()
+            .get(name)
+            .unwrap()
+            .solve(&mut NullRenderer, maze, 0, false)
+            .unwrap()
+    }
+
+    #[test]
+    fn a_star_and_dijkstra_find_same_cost() {
+        for seed in 0..10 {
+            let maze = looped_maze(seed);
+            let a_star = solve("a_star", &maze);
+            let dijkstra = solve("dijkstra", &maze);
+
+            assert!(a_star.reached && dijkstra.reached);
+            assert_eq!(a_star.cost, dijkstra.cost, "seed {seed}");
+            assert_eq!(dijkstra.cost, maze.path_cost(&dijkstra.path));
+        }
+    }
+
+    #[test]
+    fn breadth_first_and_bidirectional_find_same_length() {
+        for seed in 0..10 {
+            let maze = looped_maze(seed);
+            let breadth_first = solve("breadth_first_search", &maze);
+            let bidirectional = solve("bidirectional", &maze);
+
+            assert!(breadth_first.reached && bidirectional.reached);
+            assert_eq!(
+                breadth_first.path.len(),
+                bidirectional.path.len(),
+                "seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn agents_stop_when_goal_is_off_their_wall() {
+        for seed in 0..10 {
+            // Removing the dead ends cuts walls loose from the outer wall, so the goal in the middle is off it.
+            let mut maze = generator::Registry::default()
+                .get("kruskal")
+                .unwrap()
+                .generate(&mut NullRenderer, Maze::new_walled(20, 30), seed, 0)
+                .unwrap();
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            rng.set_stream(1);
+            maze.braid(1.0, &mut rng);
+            maze.set_goal((15, 10)).unwrap();
+
+            for name in ["left_hand", "right_hand"] {
+                let solution = solve(name, &maze);
+                assert!(!solution.reached, "{name} reached the goal for seed {seed}");
+                assert!(
+                    solution.path.is_empty(),
+                    "{name} kept a path for seed {seed}"
+                );
+            }
+
+            let pledge = solve("pledge", &maze);
+            match pledge.reached {
+                true => {
+                    assert_eq!(pledge.path.first(), Some(&maze.start()));
+                    assert_eq!(pledge.path.last(), Some(&maze.goal()));
+                }
+                false => assert!(pledge.path.is_empty(), "pledge kept a path for seed {seed}"),
+            }
+        }
+    }
+}
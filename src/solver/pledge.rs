@@ -0,0 +1,118 @@
+use crate::maze::{Maze, Side};
+use crate::render::Renderer;
+use crate::solver::wall_follower::Walk;
+use crate::solver::{Solution, Solver};
+use anyhow::Result;
+use std::collections::HashSet;
+
+/// Number of steps per cell after which the Pledge solver gives up.
+const STEPS_PER_CELL: usize = 64;
+
+/// Solver heading in one direction and following walls only to get around them, see [`solve`].
+pub struct Pledge;
+
+impl Solver for Pledge {
+    fn name(&self) -> &'static str {
+        "pledge"
+    }
+
+    fn description(&self) -> &'static str {
+        "Heads towards the exit and follows walls until it has turned back the same amount, escapes islands of walls"
+    }
+
+    fn solve(
+        &self,
+        renderer: &mut dyn Renderer,
+        maze: &Maze,
+        delay: u64,
+        trace: bool,
+    ) -> Result<Solution> {
+        solve(renderer, maze, delay, trace)
+    }
+}
+
+/// Walks from the start towards the exit of the goal, only knowing the walls around the current cell. In pseudocode
+/// the algorithm looks like this:
+/// ```text
+/// heading = direction
+/// loop {
+///     if turns == 0 && !wall(heading) {
+///         move(heading);
+///     } else {
+///         // Follow the wall with the left hand, counting quarter turns left as 1 and right as -1.
+///         heading = first_open([left, straight, right, back]);
+///         turns += turn;
+///         move(heading);
+///     }
+/// }
+/// ```
+/// Because it only leaves the wall after undoing all its turns, it does not circle islands of walls like a wall
+/// follower. It still stops and reports that the goal was not reached when it comes back to a cell in the same state,
+/// or takes too many steps, as a goal inside the maze can be missed.
+pub fn solve(
+    renderer: &mut dyn Renderer,
+    maze: &Maze,
+    delay: u64,
+    trace: bool,
+) -> Result<Solution> {
+    // Head out through the exit, or towards the goal if it has none.
+    let direction = maze.opening(maze.goal()).unwrap_or_else(|| {
+        let ((x1, y1), (x2, y2)) = (maze.start(), maze.goal());
+        match (x1.abs_diff(x2) >= y1.abs_diff(y2), x2 > x1, y2 > y1) {
+            (true, true, _) => Side::Right,
+            (true, false, _) => Side::Left,
+            (false, _, true) => Side::Bottom,
+            (false, _, false) => Side::Top,
+        }
+    });
+
+    let mut heading = direction;
+    let mut turns: i32 = 0;
+    let mut walk = Walk::new(renderer, maze, heading)?;
+    let mut states = HashSet::new();
+    let limit = STEPS_PER_CELL * maze.cells().count();
+
+    let reached = loop {
+        if walk.cell() == maze.goal() {
+            break true;
+        }
+        if !states.insert((walk.cell(), heading, turns)) || states.len() > limit {
+            break false;
+        }
+
+        // Walk straight on in the direction until a wall is hit.
+        if turns == 0 && walk.can_move(maze, heading) {
+            walk.step(renderer, maze, heading, delay, trace)?;
+            continue;
+        }
+
+        // Turn right at the wall ahead, so it lies at the left hand, then keep following it.
+        let sides = match turns {
+            0 => [
+                (heading.right(), -1),
+                (heading.opposite(), -2),
+                (heading.left(), 1),
+            ]
+            .to_vec(),
+            _ => [
+                (heading.left(), 1),
+                (heading, 0),
+                (heading.right(), -1),
+                (heading.opposite(), -2),
+            ]
+            .to_vec(),
+        };
+        let Some((side, turn)) = sides
+            .into_iter()
+            .find(|&(side, _)| walk.can_move(maze, side))
+        else {
+            break false;
+        };
+
+        heading = side;
+        turns += turn;
+        walk.step(renderer, maze, heading, delay, trace)?;
+    };
+
+    walk.finish(renderer, maze, reached, delay)
+}
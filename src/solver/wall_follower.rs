@@ -0,0 +1,208 @@
+use crate::maze::{Cell, Maze, Side};
+use crate::render::Renderer;
+use crate::solver::{draw_path, Solution, Solver};
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+use spin_sleep::sleep;
+use std::time::Duration;
+
+/// Hand that a wall follower keeps on the wall.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+impl Hand {
+    /// Returns the side reached by turning towards this hand, when facing the given side.
+    pub fn turn(self, heading: Side) -> Side {
+        match self {
+            Hand::Left => heading.left(),
+            Hand::Right => heading.right(),
+        }
+    }
+}
+
+/// Solver walking through the maze with one hand on the wall, see [`solve`].
+pub struct WallFollower {
+    hand: Hand,
+}
+
+impl WallFollower {
+    pub fn new(hand: Hand) -> WallFollower {
+        WallFollower { hand }
+    }
+}
+
+impl Solver for WallFollower {
+    fn name(&self) -> &'static str {
+        match self.hand {
+            Hand::Left => "left_hand",
+            Hand::Right => "right_hand",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self.hand {
+            Hand::Left => {
+                "Walks with the left hand on the wall, fails when the goal is not on the same wall"
+            }
+            Hand::Right => {
+                "Walks with the right hand on the wall, fails when the goal is not on the same wall"
+            }
+        }
+    }
+
+    fn solve(
+        &self,
+        renderer: &mut dyn Renderer,
+        maze: &Maze,
+        delay: u64,
+        trace: bool,
+    ) -> Result<Solution> {
+        solve(renderer, maze, self.hand, delay, trace)
+    }
+}
+
+/// Walks from the start with one hand on the wall, only knowing the walls around the current cell. At every cell it
+/// turns towards the hand if it can, else goes straight on, else turns away from the hand and else turns back.
+/// In a perfect maze this always reaches the goal, but with loops it can circle an island of walls forever. Coming back
+/// to a cell with the same heading means it is circling, so it stops and reports that the goal was not reached.
+pub fn solve(
+    renderer: &mut dyn Renderer,
+    maze: &Maze,
+    hand: Hand,
+    delay: u64,
+    trace: bool,
+) -> Result<Solution> {
+    // Face into the maze through the entrance.
+    let mut heading = maze
+        .opening(maze.start())
+        .map_or(Side::Bottom, Side::opposite);
+    let mut walk = Walk::new(renderer, maze, heading)?;
+    let mut states = HashSet::new();
+
+    let reached = loop {
+        if walk.cell() == maze.goal() {
+            break true;
+        }
+        if !states.insert((walk.cell(), heading)) {
+            break false;
+        }
+
+        let sides = [
+            hand.turn(heading),
+            heading,
+            hand.turn(heading).opposite(),
+            heading.opposite(),
+        ];
+        let Some(side) = sides.into_iter().find(|&side| walk.can_move(maze, side)) else {
+            break false;
+        };
+
+        heading = side;
+        walk.step(renderer, maze, heading, delay, trace)?;
+    };
+
+    walk.finish(renderer, maze, reached, delay)
+}
+
+/// Route of an agent that only knows the walls around its current cell, drawn as an arrow in its heading.
+pub(crate) struct Walk {
+    cell: Cell,
+    /// Route from the start to the current cell, with every loop walked erased.
+    route: Vec<Cell>,
+    positions: HashMap<Cell, usize>,
+    visited: HashSet<Cell>,
+    order: Vec<Cell>,
+}
+
+impl Walk {
+    /// Starts a walk at the start of the maze, drawing the agent facing the heading.
+    pub(crate) fn new(renderer: &mut dyn Renderer, maze: &Maze, heading: Side) -> Result<Walk> {
+        let start = maze.start();
+        renderer.mark_cell(maze, start, heading.arrow())?;
+
+        Ok(Walk {
+            cell: start,
+            route: vec![start],
+            positions: HashMap::from([(start, 0)]),
+            visited: HashSet::from([start]),
+            order: vec![start],
+        })
+    }
+
+    /// Returns the cell the agent stands in.
+    pub(crate) fn cell(&self) -> Cell {
+        self.cell
+    }
+
+    /// Returns whether there is a passage on the given side of the current cell.
+    pub(crate) fn can_move(&self, maze: &Maze, side: Side) -> bool {
+        maze.neighbor(self.cell, side)
+            .is_some_and(|neighbor| maze.has_passage(self.cell, neighbor))
+    }
+
+    /// Moves the agent through the given side of the current cell, which should have a passage.
+    pub(crate) fn step(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        maze: &Maze,
+        side: Side,
+        delay: u64,
+        trace: bool,
+    ) -> Result<()> {
+        let from = self.cell;
+        self.cell = maze
+            .neighbor(from, side)
+            .expect("agents only move through passages");
+
+        if self.visited.insert(self.cell) {
+            self.order.push(self.cell);
+        }
+
+        // Erase the loop if the cell is already on the route.
+        match self.positions.get(&self.cell) {
+            Some(&position) => {
+                for cell in self.route.drain(position + 1..) {
+                    self.positions.remove(&cell);
+                }
+            }
+            None => {
+                self.positions.insert(self.cell, self.route.len());
+                self.route.push(self.cell);
+            }
+        }
+
+        // Move the arrow, leaving a dot behind when tracing.
+        sleep(Duration::from_millis(delay));
+        match trace {
+            true => renderer.mark_cell(maze, from, '·')?,
+            false => renderer.draw_cell(maze, from)?,
+        }
+        renderer.mark_cell(maze, self.cell, side.arrow())
+    }
+
+    /// Ends the walk, drawing the route to the goal if it was reached.
+    pub(crate) fn finish(
+        self,
+        renderer: &mut dyn Renderer,
+        maze: &Maze,
+        reached: bool,
+        delay: u64,
+    ) -> Result<Solution> {
+        let path = if reached { self.route } else { Vec::new() };
+
+        let solution = Solution {
+            cost: maze.path_cost(&path),
+            path,
+            visited: self.order,
+            reached,
+        };
+
+        draw_path(renderer, maze, &solution.path, delay)?;
+
+        Ok(solution)
+    }
+}
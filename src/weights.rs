@@ -0,0 +1,184 @@
+use crate::maze::Cell;
+use anyhow::{bail, Result};
+use rand::Rng;
+use std::fs;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Characters used to shade cells, from the cheapest to the most expensive.
+const SHADES: [char; 4] = [' ', '░', '▒', '▓'];
+
+/// Number of cells between the random values that noise is interpolated from.
+const NOISE_SCALE: u16 = 6;
+
+/// The Weights struct stores the cost of entering every cell of a maze, which is at least 1.
+///
+/// Weights are read from text files, where every line is a row of costs separated by whitespace. For example:
+/// ```text
+/// 1 1 2 5
+/// 1 3 9 5
+/// 1 1 2 1
+/// ```
+#[derive(Clone, Debug)]
+pub struct Weights {
+    rows: u16,
+    columns: u16,
+    costs: Vec<u32>,
+    min: u32,
+    max: u32,
+}
+
+impl Weights {
+    /// Returns weights from rows of costs. Fails unless all rows have the same length and all costs are at least 1.
+    pub fn new(rows: Vec<Vec<u32>>) -> Result<Weights> {
+        let width = rows.first().map_or(0, Vec::len);
+        if width == 0 {
+            bail!(WeightsError::Empty);
+        }
+        let Ok(height) = u16::try_from(rows.len()) else {
+            bail!(WeightsError::TooManyRows);
+        };
+        let Ok(width) = u16::try_from(width) else {
+            bail!(WeightsError::TooManyColumns);
+        };
+
+        let mut costs = Vec::with_capacity(height as usize * width as usize);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width as usize {
+                bail!(WeightsError::VaryingRowLengths(y + 1));
+            }
+            if let Some(x) = row.iter().position(|&cost| cost == 0) {
+                bail!(WeightsError::ZeroCost(y + 1, x + 1));
+            }
+            costs.extend(row);
+        }
+
+        Ok(Weights::from_costs(height, width, costs))
+    }
+
+    /// Parses weights from path, ignoring empty lines.
+    pub fn from_path(path: PathBuf) -> Result<Weights> {
+        let text = fs::read_to_string(path)?;
+
+        let rows = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(y, line)| {
+                line.split_whitespace()
+                    .enumerate()
+                    .map(|(x, cost)| {
+                        cost.parse()
+                            .map_err(|_| WeightsError::BadCost(y + 1, x + 1, cost.to_string()))
+                    })
+                    .collect::<Result<Vec<u32>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Weights::new(rows)
+    }
+
+    /// Returns weights between 1 and max that change smoothly from cell to cell, like hills and valleys.
+    /// The same random numbers always result in the same weights.
+    pub fn noise(rows: u16, columns: u16, max: u32, rng: &mut impl Rng) -> Weights {
+        // Random values on a coarse lattice, which every cell interpolates between.
+        let lattice_rows = rows as usize / NOISE_SCALE as usize + 2;
+        let lattice_columns = columns as usize / NOISE_SCALE as usize + 2;
+        let lattice: Vec<f64> = (0..lattice_rows * lattice_columns)
+            .map(|_| rng.gen())
+            .collect();
+        let value = |lx: usize, ly: usize| lattice[ly * lattice_columns + lx];
+
+        let mut costs = Vec::with_capacity(rows as usize * columns as usize);
+        for y in 0..rows {
+            for x in 0..columns {
+                let (lx, tx) = lattice_position(x);
+                let (ly, ty) = lattice_position(y);
+
+                let top = value(lx, ly) + (value(lx + 1, ly) - value(lx, ly)) * tx;
+                let bottom = value(lx, ly + 1) + (value(lx + 1, ly + 1) - value(lx, ly + 1)) * tx;
+                let noise = top + (bottom - top) * ty;
+
+                costs.push((1 + (noise * max as f64) as u32).min(max.max(1)));
+            }
+        }
+
+        Weights::from_costs(rows, columns, costs)
+    }
+
+    /// Returns weights from costs stored row by row.
+    fn from_costs(rows: u16, columns: u16, costs: Vec<u32>) -> Weights {
+        Weights {
+            rows,
+            columns,
+            min: costs.iter().min().copied().unwrap_or(1),
+            max: costs.iter().max().copied().unwrap_or(1),
+            costs,
+        }
+    }
+
+    /// Returns the number of cell rows.
+    pub fn rows(&self) -> u16 {
+        self.rows
+    }
+
+    /// Returns the number of cell columns.
+    pub fn columns(&self) -> u16 {
+        self.columns
+    }
+
+    /// Returns the lowest cost of any cell.
+    pub fn min(&self) -> u32 {
+        self.min
+    }
+
+    /// Returns the highest cost of any cell.
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+
+    /// Returns the cost of entering the cell. Panics if the cell lies outside of the weights.
+    pub fn cost(&self, (x, y): Cell) -> u32 {
+        assert!(
+            x < self.columns && y < self.rows,
+            "({x}, {y}) lies outside of the weights"
+        );
+        self.costs[y as usize * self.columns as usize + x as usize]
+    }
+
+    /// Returns the character a cell is shaded with, darker for more expensive cells.
+    pub fn shade(&self, cell: Cell) -> char {
+        if self.min == self.max {
+            return SHADES[0];
+        }
+
+        // Round to the nearest shade.
+        let range = (self.max - self.min) as usize;
+        let level = (self.cost(cell) - self.min) as usize * (SHADES.len() - 1);
+        SHADES[(level + range / 2) / range]
+    }
+}
+
+/// Returns the lattice index before a cell coordinate and how far the cell lies towards the next index, smoothed so
+/// the noise has no visible creases.
+fn lattice_position(coordinate: u16) -> (usize, f64) {
+    let t = (coordinate % NOISE_SCALE) as f64 / NOISE_SCALE as f64;
+    ((coordinate / NOISE_SCALE) as usize, t * t * (3.0 - 2.0 * t))
+}
+
+/// Errors that can occur while reading weights.
+#[derive(Error, Debug)]
+pub enum WeightsError {
+    #[error("There are no weights.")]
+    Empty,
+    #[error("Bad cost '{2}' found at {0}:{1}.")]
+    BadCost(usize, usize, String),
+    #[error("Cost at {0}:{1} is 0, costs should be at least 1.")]
+    ZeroCost(usize, usize),
+    #[error("Varying number of costs for row {0}.")]
+    VaryingRowLengths(usize),
+    #[error("Number of rows exceeds u16::MAX (65535).")]
+    TooManyRows,
+    #[error("Number of columns exceeds u16::MAX (65535).")]
+    TooManyColumns,
+}